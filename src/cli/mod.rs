@@ -1,2 +1,5 @@
 pub mod repl;
 pub mod runner;
+
+#[cfg(test)]
+mod repl_tests;
@@ -1,50 +1,62 @@
-use std::error::Error;
+use std::{error::Error, path::PathBuf};
 
 use rustyline::{DefaultEditor, error::ReadlineError};
 
-use crate::frontend::{lexer::Lexer, parser::Parser};
+use crate::{
+    cli::runner::{report, report_exception},
+    diagnostics::{render::Diagnostic, source::SourceFile},
+    frontend::{
+        lexer::{Lexer, TabPolicy},
+        parser::Parser,
+    },
+    runtime::interpreter::Interpreter,
+};
 
 pub struct Repl {
+    buf: String,
     indent_count: usize,
     indented: bool,
+    continued: bool,
+    inline_suite: bool,
+    tab_policy: TabPolicy,
+    interpreter: Interpreter,
 }
 
 impl Repl {
-    pub fn new() -> Self {
+    pub fn new(tab_policy: TabPolicy) -> Self {
+        let mut interpreter = Interpreter::new();
+        interpreter.add_search_path(PathBuf::from("."));
+
         Self {
+            buf: String::new(),
             indent_count: 0,
             indented: false,
+            continued: false,
+            inline_suite: false,
+            tab_policy,
+            interpreter,
         }
     }
 
     pub fn run_repl(&mut self) -> Result<(), Box<dyn Error>> {
-        let mut buf = String::new();
         let mut rl = DefaultEditor::new()?;
 
         loop {
-            self.indented = self.indent_count > 0;
+            self.indented = self.indent_count > 0 || self.inline_suite;
             let indent = "    ".repeat(self.indent_count);
-            let prompt = if !self.indented { ">>> " } else { "... " };
+            let prompt = if !self.indented && !self.continued {
+                ">>> "
+            } else {
+                "... "
+            };
             let readline = rl.readline_with_initial(prompt, (&indent, ""));
 
             match readline {
                 Ok(line) => {
                     let _ = rl.add_history_entry(&line);
 
-                    if line.trim().is_empty() {
-                        self.indent_count = self.indent_count.saturating_sub(1);
-                        continue;
-                    }
-
-                    buf.push_str(&line);
-                    buf.push('\n');
-
-                    if line.trim().ends_with(':') {
-                        self.indent_count += 1;
-                    }
-
-                    if line.trim().starts_with("return") {
-                        self.indent_count = 0;
+                    if let Some(src) = self.push_line(&line) {
+                        self.execute(&src);
                     }
                 }
                 // Ctrl+C | Ctrl+D
@@ -57,22 +69,104 @@ impl Repl {
             }
         }
 
-        println!("{buf}");
+        Ok(())
+    }
+
+    // Adds a line of input, returning the buffered source once it forms a
+    // complete statement.
+    pub fn push_line(&mut self, line: &str) -> Option<String> {
+        if line.trim().is_empty() && !self.continued {
+            self.indent_count = self.indent_count.saturating_sub(1);
+            self.inline_suite = false;
+        } else {
+            self.buf.push_str(line);
+            self.buf.push('\n');
+
+            if line.trim().ends_with(':') {
+                self.indent_count += 1;
+            } else if starts_compound_statement(line) {
+                // A header with its suite on the same line, like `try: f()`,
+                // may still be followed by an `except:` or `else:` clause, so
+                // it runs only once a blank line ends it.
+                self.inline_suite = true;
+            }
+
+            if line.trim().starts_with("return") {
+                self.indent_count = 0;
+            }
+        }
+
+        self.continued = line.trim_end().ends_with('\\') || open_brackets(&self.buf) > 0;
 
-        let mut lexer = Lexer::new(&buf);
-        lexer.analyze();
+        if self.indent_count == 0 && !self.inline_suite && !self.continued && !self.buf.is_empty() {
+            Some(std::mem::take(&mut self.buf))
+        } else {
+            None
+        }
+    }
 
-        let lex_tokens = lexer.tokens;
-        println!("TOKENS -------------------------------");
-        println!("{lex_tokens:#?}");
+    fn execute(&mut self, src: &String) {
+        let source = SourceFile::new("<stdin>", src);
+        let mut lexer = Lexer::new(src).with_tab_policy(self.tab_policy);
+        let lex_tokens = match lexer.analyze() {
+            Ok(tokens) => tokens,
+            Err(errors) => {
+                report(&source, errors.iter().map(Diagnostic::from));
+                return;
+            }
+        };
 
         let mut parser = Parser::new(&lex_tokens);
-        parser.parse();
+        let stmts = match parser.parse() {
+            Ok(stmts) => stmts,
+            Err(errors) => {
+                report(&source, errors.iter().map(Diagnostic::from));
+                return;
+            }
+        };
+
+        if let Err(exc) = self.interpreter.run_interactive(source, &stmts) {
+            report_exception(&exc);
+        }
+    }
+}
 
-        let stmts = parser.statements;
-        println!("STATEMENTS -------------------------------");
-        println!("{stmts:#?}");
+fn starts_compound_statement(line: &str) -> bool {
+    let line = line.trim_start();
+    let keyword = line
+        .split(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+        .next()
+        .unwrap_or_default();
+    matches!(
+        keyword,
+        "if" | "elif" | "else" | "while" | "for" | "try" | "except" | "finally" | "def" | "class"
+    )
+}
 
-        Ok(())
+// Counts brackets left open in `src`, skipping over strings and comments, so
+// that a bracketed expression can span several input lines.
+fn open_brackets(src: &str) -> usize {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut chars = src.chars();
+
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(_), '\n') => quote = None,
+            (Some(q), ch) if ch == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(ch),
+            (None, '#') => {
+                chars.by_ref().find(|&ch| ch == '\n');
+            }
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
     }
+
+    depth
 }
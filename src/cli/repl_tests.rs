@@ -0,0 +1,48 @@
+use crate::frontend::lexer::TabPolicy;
+
+use super::repl::Repl;
+
+fn push_lines(repl: &mut Repl, lines: &[&str]) -> Vec<Option<String>> {
+    lines.iter().map(|line| repl.push_line(line)).collect()
+}
+
+#[test]
+fn test_simple_statement_runs_right_away() {
+    let mut repl = Repl::new(TabPolicy::default());
+    assert_eq!(repl.push_line("x = 1"), Some("x = 1\n".to_string()));
+}
+
+#[test]
+fn test_inline_suite_waits_for_blank_line() {
+    let mut repl = Repl::new(TabPolicy::default());
+    assert_eq!(
+        push_lines(&mut repl, &["try: 1/0", "except: print 'caught'", ""]),
+        [
+            None,
+            None,
+            Some("try: 1/0\nexcept: print 'caught'\n".to_string())
+        ]
+    );
+
+    assert_eq!(
+        push_lines(&mut repl, &["if x: a", "else: b", ""]),
+        [None, None, Some("if x: a\nelse: b\n".to_string())]
+    );
+}
+
+#[test]
+fn test_block_after_inline_suite() {
+    let mut repl = Repl::new(TabPolicy::default());
+    assert_eq!(
+        push_lines(
+            &mut repl,
+            &["try: f()", "except:", "    print 'caught'", ""]
+        ),
+        [
+            None,
+            None,
+            None,
+            Some("try: f()\nexcept:\n    print 'caught'\n".to_string())
+        ]
+    );
+}
@@ -1,25 +1,65 @@
-use std::{error::Error, fs::read_to_string};
+use std::{
+    error::Error,
+    fs::read_to_string,
+    io::{Write, stderr},
+    path::Path,
+};
 
-use crate::frontend::{lexer::Lexer, parser::Parser};
+use crate::{
+    diagnostics::{render::Diagnostic, source::SourceFile},
+    frontend::{
+        lexer::{Lexer, TabPolicy},
+        parser::Parser,
+    },
+    runtime::{exception::Exception, interpreter::Interpreter},
+};
 
-pub fn run_file(path: String) -> Result<(), Box<dyn Error>> {
-    let input = read_to_string(path)?;
+pub fn run_file(path: String, tab_policy: TabPolicy) -> Result<i32, Box<dyn Error>> {
+    let input = read_to_string(&path)?;
+    let source = SourceFile::new(&path, &input);
 
-    println!("{input}");
+    let mut lexer = Lexer::new(&input).with_tab_policy(tab_policy);
+    let lex_tokens = match lexer.analyze() {
+        Ok(tokens) => tokens,
+        Err(errors) => {
+            report(&source, errors.iter().map(Diagnostic::from));
+            return Ok(1);
+        }
+    };
 
-    let mut lexer = Lexer::new(&input);
-    lexer.analyze();
+    let mut parser = Parser::new(&lex_tokens);
+    let stmts = match parser.parse() {
+        Ok(stmts) => stmts,
+        Err(errors) => {
+            report(&source, errors.iter().map(Diagnostic::from));
+            return Ok(1);
+        }
+    };
 
-    let lex_tokens = lexer.tokens;
-    println!("TOKENS -------------------------------");
-    println!("{lex_tokens:#?}");
+    let mut interpreter = Interpreter::new();
+    if let Some(dir) = Path::new(&path).parent() {
+        interpreter.add_search_path(dir.to_path_buf());
+    }
 
-    let mut parser = Parser::new(&lex_tokens);
-    parser.parse();
+    if let Err(exc) = interpreter.run(source, &stmts) {
+        report_exception(&exc);
+        return Ok(1);
+    }
 
-    let stmts = parser.statements;
-    println!("STATEMENTS -------------------------------");
-    println!("{stmts:#?}");
+    Ok(0)
+}
+
+pub fn report(source: &SourceFile, diagnostics: impl Iterator<Item = Diagnostic>) {
+    for diagnostic in diagnostics {
+        eprintln!("{}", diagnostic.render(source));
+    }
+}
 
-    Ok(())
+// Tracebacks go out as bytes, like the program's output, so string values in
+// them are not re-encoded.
+pub fn report_exception(exc: &Exception) {
+    let mut err = stderr().lock();
+    let _ = err
+        .write_all(&exc.traceback())
+        .and_then(|_| err.write_all(b"\n"));
 }
@@ -1,11 +1,23 @@
-use crate::core::token::{LiteralValue, TokenType};
+use crate::core::token::{LiteralValue, Span, TokenType};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+impl Stmt {
+    pub fn new(kind: StmtKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[allow(dead_code)]
-pub enum Stmt {
+pub enum StmtKind {
     FunctionDef {
         name: String,
-        params: Vec<String>,
+        params: Parameters,
         body: Vec<Stmt>,
     },
     ClassDef {
@@ -23,8 +35,12 @@ pub enum Stmt {
     While {
         condition: Expr,
         body: Vec<Stmt>,
+        else_branch: Option<Vec<Stmt>>,
+    },
+    Print {
+        items: Vec<Expr>,
+        trailing_comma: bool,
     },
-    Print(Expr),
     Assign {
         target: Target,
         value: Expr,
@@ -33,6 +49,7 @@ pub enum Stmt {
         target: Target,
         iterable: Expr,
         body: Vec<Stmt>,
+        else_branch: Option<Vec<Stmt>>,
     },
     Block(Vec<Stmt>),
     Import(Vec<String>),
@@ -43,9 +60,13 @@ pub enum Stmt {
     Global(Vec<String>),
     Try {
         body: Vec<Stmt>,
-        except_clauses: Vec<(Option<Expr>, Vec<Stmt>)>,
+        except_clauses: Vec<ExceptClause>,
+        finally_body: Option<Vec<Stmt>>,
+    },
+    Raise {
+        exception: Option<Expr>,
+        value: Option<Expr>,
     },
-    Raise(Option<Expr>),
     Del(Target),
     Pass,
     Break,
@@ -66,6 +87,11 @@ pub enum Expr {
         op: TokenType,
         right: Box<Expr>,
     },
+    Compare {
+        left: Box<Expr>,
+        ops: Vec<CmpOp>,
+        comparators: Vec<Expr>,
+    },
     Grouping(Box<Expr>),
     Call {
         callee: Box<Expr>,
@@ -84,19 +110,67 @@ pub enum Expr {
         value: Box<Expr>,
     },
     Lambda {
-        params: Vec<String>,
+        params: Parameters,
         body: Box<Expr>,
     },
     Index {
         object: Box<Expr>,
         index: Box<Expr>,
     },
+    Slice {
+        object: Box<Expr>,
+        lower: Option<Box<Expr>>,
+        upper: Option<Box<Expr>>,
+    },
+}
+
+/// The parameter list of a `def` or `lambda`. Positional parameters are
+/// names or nested tuples to unpack, and `defaults` belong to the last of them.
+#[derive(Clone, Debug, Default)]
+pub struct Parameters {
+    pub positional: Vec<Target>,
+    pub defaults: Vec<Expr>,
+    pub varargs: Option<String>,
 }
 
-#[derive(Debug)]
+/// An `except [matcher [, target]]:` handler of a `try` statement.
+#[derive(Clone, Debug)]
+pub struct ExceptClause {
+    pub matcher: Option<Expr>,
+    pub target: Option<Target>,
+    pub body: Vec<Stmt>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CmpOp {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Is,
+    IsNot,
+    In,
+    NotIn,
+}
+
+#[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub enum Target {
     Name(String),
     Tuple(Vec<Target>),
-    Attribute { object: Box<Expr>, name: String },
+    Attribute {
+        object: Box<Expr>,
+        name: String,
+    },
+    Index {
+        object: Box<Expr>,
+        index: Box<Expr>,
+    },
+    Slice {
+        object: Box<Expr>,
+        lower: Option<Box<Expr>>,
+        upper: Option<Box<Expr>>,
+    },
 }
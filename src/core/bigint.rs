@@ -0,0 +1,541 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Not, Shl, Shr, Sub},
+};
+
+/// An arbitrary-precision signed integer, stored as a sign and a magnitude of
+/// little-endian base 2^32 digits with no trailing zero digits.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    digits: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn from_i64(value: i64) -> Self {
+        Self::from_i128(value.into())
+    }
+
+    pub fn from_i128(value: i128) -> Self {
+        let magnitude = value.unsigned_abs();
+        let digits = (0..4).map(|i| (magnitude >> (32 * i)) as u32).collect();
+        Self::from_parts(value < 0, digits)
+    }
+
+    /// Converts a float to an integer, truncating towards zero. Returns `None`
+    /// for infinities and NaN.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let value = value.trunc();
+        if value.abs() < 9.223_372_036_854_776e18 {
+            return Some(Self::from_i64(value as i64));
+        }
+
+        // Floats this large are integral, so shifting the mantissa by the
+        // exponent is exact.
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as usize - 1075;
+        let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+        let magnitude = &Self::from_i64(mantissa as i64) << exponent;
+        Some(if value < 0.0 { -&magnitude } else { magnitude })
+    }
+
+    fn from_parts(negative: bool, mut digits: Vec<u32>) -> Self {
+        trim(&mut digits);
+        Self {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+
+    /// Parses an optionally signed run of digits in the given radix (2-36).
+    pub fn from_str_radix(text: &str, radix: u32) -> Option<Self> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+
+        if text.is_empty() {
+            return None;
+        }
+
+        let mut digits = vec![];
+        for ch in text.chars() {
+            let digit = ch.to_digit(radix)?;
+            mul_add_small(&mut digits, radix, digit);
+        }
+
+        Some(Self::from_parts(negative, digits))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        if self.digits.len() > 2 {
+            return None;
+        }
+
+        let magnitude = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &digit| (acc << 32) | digit as u64);
+
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    /// Converts to the nearest float, or an infinity if the value is too large.
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &digit| acc * 4_294_967_296.0 + digit as f64);
+
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        let mut digits = self.digits.clone();
+        let mut text = vec![];
+        while !digits.is_empty() {
+            let digit = div_rem_small(&mut digits, radix);
+            text.push(char::from_digit(digit, radix).unwrap_or('?'));
+        }
+
+        if self.negative {
+            text.push('-');
+        }
+
+        text.iter().rev().collect()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::from_parts(false, self.digits.clone())
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = Self::from_i64(1);
+        let mut base = self.clone();
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// Floor division and modulo, so the remainder takes the divisor's sign.
+    /// Returns `None` when dividing by zero.
+    pub fn div_mod_floor(&self, other: &Self) -> Option<(Self, Self)> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let (quotient, remainder) = div_rem_magnitudes(&self.digits, &other.digits);
+        let quotient = Self::from_parts(self.negative != other.negative, quotient);
+        let remainder = Self::from_parts(self.negative, remainder);
+
+        if !remainder.is_zero() && remainder.negative != other.negative {
+            Some((&quotient - &Self::from_i64(1), &remainder + other))
+        } else {
+            Some((quotient, remainder))
+        }
+    }
+
+    // Bitwise operators act on an infinite two's complement representation,
+    // so both operands are sign-extended one digit past the longer of the two.
+    fn bitwise(&self, other: &Self, op: impl Fn(u32, u32) -> u32) -> Self {
+        let len = self.digits.len().max(other.digits.len()) + 1;
+        let mut digits = self
+            .twos_complement(len)
+            .into_iter()
+            .zip(other.twos_complement(len))
+            .map(|(a, b)| op(a, b))
+            .collect::<Vec<_>>();
+
+        let negative = digits.last().is_some_and(|&digit| digit >> 31 == 1);
+        if negative {
+            negate_digits(&mut digits);
+        }
+        Self::from_parts(negative, digits)
+    }
+
+    fn twos_complement(&self, len: usize) -> Vec<u32> {
+        let mut digits = self.digits.clone();
+        digits.resize(len, 0);
+        if self.negative {
+            negate_digits(&mut digits);
+        }
+        digits
+    }
+}
+
+fn trim(digits: &mut Vec<u32>) {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+}
+
+fn negate_digits(digits: &mut [u32]) {
+    let mut carry = true;
+    for digit in digits.iter_mut() {
+        *digit = !*digit;
+        if carry {
+            (*digit, carry) = digit.overflowing_add(1);
+        }
+    }
+}
+
+fn mul_add_small(digits: &mut Vec<u32>, mul: u32, add: u32) {
+    let mut carry = add as u64;
+
+    for digit in digits.iter_mut() {
+        let value = *digit as u64 * mul as u64 + carry;
+        *digit = value as u32;
+        carry = value >> 32;
+    }
+
+    if carry > 0 {
+        digits.push(carry as u32);
+    }
+}
+
+fn div_rem_small(digits: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut rem = 0u64;
+
+    for digit in digits.iter_mut().rev() {
+        let value = (rem << 32) | *digit as u64;
+        *digit = (value / divisor as u64) as u32;
+        rem = value % divisor as u64;
+    }
+
+    trim(digits);
+    rem as u32
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+
+    for (i, &digit) in long.iter().enumerate() {
+        let sum = digit as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+// Requires `a` to be at least as large as `b`.
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+
+    for (i, &digit) in a.iter().enumerate() {
+        let diff = digit as i64 - b.get(i).copied().unwrap_or(0) as i64 + borrow;
+        result.push(diff as u32);
+        borrow = diff >> 32;
+    }
+
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let value = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = value as u32;
+            carry = value >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+
+    trim(&mut result);
+    result
+}
+
+fn shl_magnitude(digits: &[u32], bits: usize) -> Vec<u32> {
+    if digits.is_empty() {
+        return vec![];
+    }
+
+    let (words, bits) = (bits / 32, (bits % 32) as u32);
+    let mut result = vec![0u32; words];
+    if bits == 0 {
+        result.extend_from_slice(digits);
+    } else {
+        let mut carry = 0u32;
+        for &digit in digits {
+            result.push((digit << bits) | carry);
+            carry = digit >> (32 - bits);
+        }
+        result.push(carry);
+    }
+
+    trim(&mut result);
+    result
+}
+
+fn shr_magnitude(digits: &[u32], bits: usize) -> Vec<u32> {
+    let (words, bits) = (bits / 32, (bits % 32) as u32);
+    if words >= digits.len() {
+        return vec![];
+    }
+
+    let digits = &digits[words..];
+    let mut result = if bits == 0 {
+        digits.to_vec()
+    } else {
+        (0..digits.len())
+            .map(|i| {
+                let high = digits.get(i + 1).map_or(0, |&digit| digit << (32 - bits));
+                (digits[i] >> bits) | high
+            })
+            .collect()
+    };
+
+    trim(&mut result);
+    result
+}
+
+/// Truncating division of magnitudes, using Knuth's algorithm D.
+fn div_rem_magnitudes(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_magnitude(a, b) == Ordering::Less {
+        return (vec![], a.to_vec());
+    }
+
+    if let [divisor] = b {
+        let mut quotient = a.to_vec();
+        let mut remainder = vec![div_rem_small(&mut quotient, *divisor)];
+        trim(&mut remainder);
+        return (quotient, remainder);
+    }
+
+    // Normalizing so the divisor's top bit is set keeps each quotient digit
+    // estimate at most two above the true digit.
+    let shift = b[b.len() - 1].leading_zeros() as usize;
+    let divisor = shl_magnitude(b, shift);
+    let mut rem = shl_magnitude(a, shift);
+    rem.resize(a.len() + 1, 0);
+
+    let n = divisor.len();
+    let m = rem.len() - n;
+    let top = divisor[n - 1] as u64;
+    let second = divisor[n - 2] as u64;
+    let mut quotient = vec![0u32; m];
+
+    for j in (0..m).rev() {
+        let numerator = ((rem[j + n] as u64) << 32) | rem[j + n - 1] as u64;
+        let mut estimate = numerator / top;
+        let mut estimate_rem = numerator % top;
+
+        while estimate > u32::MAX as u64
+            || estimate * second > ((estimate_rem << 32) | rem[j + n - 2] as u64)
+        {
+            estimate -= 1;
+            estimate_rem += top;
+            if estimate_rem > u32::MAX as u64 {
+                break;
+            }
+        }
+
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let product = estimate * divisor[i] as u64 + carry;
+            carry = product >> 32;
+            let diff = rem[i + j] as i64 - (product & 0xffff_ffff) as i64 + borrow;
+            rem[i + j] = diff as u32;
+            borrow = diff >> 32;
+        }
+        let diff = rem[j + n] as i64 - carry as i64 + borrow;
+        rem[j + n] = diff as u32;
+
+        // The estimate was still one too large, so add the divisor back.
+        if diff < 0 {
+            estimate -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = rem[i + j] as u64 + divisor[i] as u64 + carry;
+                rem[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            rem[j + n] = rem[j + n].wrapping_add(carry as u32);
+        }
+
+        quotient[j] = estimate as u32;
+    }
+
+    trim(&mut quotient);
+    (quotient, shr_magnitude(&rem[..n], shift))
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            let digits = add_magnitudes(&self.digits, &other.digits);
+            return BigInt::from_parts(self.negative, digits);
+        }
+
+        match cmp_magnitude(&self.digits, &other.digits) {
+            Ordering::Less => {
+                let digits = sub_magnitudes(&other.digits, &self.digits);
+                BigInt::from_parts(other.negative, digits)
+            }
+            _ => {
+                let digits = sub_magnitudes(&self.digits, &other.digits);
+                BigInt::from_parts(self.negative, digits)
+            }
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let digits = mul_magnitudes(&self.digits, &other.digits);
+        BigInt::from_parts(self.negative != other.negative, digits)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.digits.clone())
+    }
+}
+
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        -&(self + &BigInt::from_i64(1))
+    }
+}
+
+impl BitAnd for &BigInt {
+    type Output = BigInt;
+
+    fn bitand(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BigInt {
+    type Output = BigInt;
+
+    fn bitor(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BigInt {
+    type Output = BigInt;
+
+    fn bitxor(self, other: &BigInt) -> BigInt {
+        self.bitwise(other, |a, b| a ^ b)
+    }
+}
+
+impl Shl<usize> for &BigInt {
+    type Output = BigInt;
+
+    fn shl(self, bits: usize) -> BigInt {
+        BigInt::from_parts(self.negative, shl_magnitude(&self.digits, bits))
+    }
+}
+
+impl Shr<usize> for &BigInt {
+    type Output = BigInt;
+
+    // Right shifts floor, so for negative values -x >> n == ~((x - 1) >> n).
+    fn shr(self, bits: usize) -> BigInt {
+        if !self.negative {
+            return BigInt::from_parts(false, shr_magnitude(&self.digits, bits));
+        }
+
+        let magnitude = &self.abs() - &BigInt::from_i64(1);
+        !&BigInt::from_parts(false, shr_magnitude(&magnitude.digits, bits))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.digits, &other.digits),
+            (true, true) => cmp_magnitude(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_str_radix(10))
+    }
+}
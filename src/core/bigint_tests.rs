@@ -0,0 +1,152 @@
+use super::bigint::BigInt;
+
+fn big(text: &str) -> BigInt {
+    BigInt::from_str_radix(text, 10).unwrap()
+}
+
+fn from_i128(value: i128) -> BigInt {
+    big(&value.to_string())
+}
+
+fn floor_div(a: i128, b: i128) -> i128 {
+    let div = a / b;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        div - 1
+    } else {
+        div
+    }
+}
+
+// A small deterministic generator, so the cross-checks below are repeatable.
+fn samples() -> Vec<i128> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut values = vec![
+        0,
+        1,
+        -1,
+        i64::MAX as i128,
+        i64::MIN as i128,
+        1 << 32,
+        -(1 << 32),
+    ];
+    for _ in 0..40 {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        let bits = state >> (state % 50);
+        let value = bits as i64 as i128;
+        values.push(if state & 1 == 0 { value } else { -value });
+    }
+    values
+}
+
+#[test]
+fn test_parse_and_format_radix() {
+    let n = big("-123456789012345678901234567890");
+    assert_eq!(n.to_string(), "-123456789012345678901234567890");
+    assert_eq!(
+        BigInt::from_str_radix("ff", 16).unwrap().to_str_radix(2),
+        "11111111"
+    );
+    assert_eq!(
+        BigInt::from_str_radix("-777", 8).unwrap().to_i64(),
+        Some(-511)
+    );
+    assert_eq!(BigInt::from_str_radix("12a", 10), None);
+    assert_eq!(BigInt::from_str_radix("", 10), None);
+    assert_eq!(big("-0").to_string(), "0");
+}
+
+#[test]
+fn test_i64_round_trip() {
+    for value in [0, 1, -1, i64::MAX, i64::MIN] {
+        assert_eq!(BigInt::from_i64(value).to_i64(), Some(value));
+    }
+    assert_eq!(big("9223372036854775808").to_i64(), None);
+    assert_eq!(big("-9223372036854775809").to_i64(), None);
+}
+
+#[test]
+fn test_float_conversions() {
+    assert_eq!(BigInt::from_f64(-3.9), Some(BigInt::from_i64(-3)));
+    assert_eq!(BigInt::from_f64(1e20), Some(big("100000000000000000000")));
+    assert_eq!(BigInt::from_f64(f64::INFINITY), None);
+    assert_eq!(big("100000000000000000000").to_f64(), 1e20);
+}
+
+#[test]
+fn test_arithmetic_matches_i128() {
+    let values = samples();
+    for &a in &values {
+        for &b in &values {
+            let (x, y) = (from_i128(a), from_i128(b));
+            assert_eq!(&x + &y, from_i128(a + b), "{a} + {b}");
+            assert_eq!(&x - &y, from_i128(a - b), "{a} - {b}");
+            assert_eq!(&x & &y, from_i128(a & b), "{a} & {b}");
+            assert_eq!(&x | &y, from_i128(a | b), "{a} | {b}");
+            assert_eq!(&x ^ &y, from_i128(a ^ b), "{a} ^ {b}");
+            if b != 0 {
+                let (div, rem) = x.div_mod_floor(&y).unwrap();
+                assert_eq!(div, from_i128(floor_div(a, b)), "{a} / {b}");
+                assert_eq!(&(&div * &y) + &rem, x, "{a} % {b}");
+            }
+        }
+        assert_eq!(!&from_i128(a), from_i128(!a));
+        assert_eq!(&from_i128(a) >> 7, from_i128(a >> 7));
+        assert_eq!(&from_i128(a) << 33, from_i128(a << 33));
+    }
+}
+
+#[test]
+fn test_multi_digit_division() {
+    let a = big("123456789012345678901234567890123456789");
+    let b = big("98765432109876543210");
+    let (div, rem) = a.div_mod_floor(&b).unwrap();
+    assert_eq!(div, big("1249999988609375000"));
+    assert_eq!(rem, big("15297067891529706789"));
+
+    let (div, rem) = (-&a).div_mod_floor(&b).unwrap();
+    assert_eq!(div, big("-1249999988609375001"));
+    assert_eq!(rem, big("83468364218346836421"));
+
+    assert_eq!(a.div_mod_floor(&BigInt::zero()), None);
+}
+
+#[test]
+fn test_pow_and_multiplication() {
+    let n = BigInt::from_i64(2).pow(100);
+    assert_eq!(n.to_string(), "1267650600228229401496703205376");
+    assert_eq!(&n * &n, BigInt::from_i64(2).pow(200));
+    assert_eq!(BigInt::from_i64(-3).pow(3), BigInt::from_i64(-27));
+    assert_eq!(BigInt::from_i64(7).pow(0), BigInt::from_i64(1));
+}
+
+#[test]
+fn test_ordering() {
+    let mut values = [
+        big("5"),
+        big("-100000000000000000000"),
+        big("0"),
+        big("100000000000000000000"),
+        big("-5"),
+    ];
+    values.sort();
+    let text = values.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        text,
+        [
+            "-100000000000000000000",
+            "-5",
+            "0",
+            "5",
+            "100000000000000000000"
+        ]
+    );
+}
+
+#[test]
+fn test_from_i128() {
+    for value in samples().into_iter().chain([i128::MIN, i128::MAX]) {
+        assert_eq!(BigInt::from_i128(value), from_i128(value), "{value}");
+    }
+}
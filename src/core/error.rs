@@ -0,0 +1,61 @@
+use std::fmt;
+
+use crate::core::token::{Span, Token, TokenType};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LexError {
+    UnexpectedChar { ch: char, span: Span },
+    UnterminatedString { span: Span },
+    InconsistentDedent { span: Span },
+    MixedIndentation { span: Span },
+    NumberOverflow { span: Span },
+    InvalidNumber { span: Span },
+}
+
+impl LexError {
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::UnexpectedChar { span, .. }
+            | LexError::UnterminatedString { span }
+            | LexError::InconsistentDedent { span }
+            | LexError::MixedIndentation { span }
+            | LexError::NumberOverflow { span }
+            | LexError::InvalidNumber { span } => *span,
+        }
+    }
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::UnexpectedChar { ch, .. } => write!(f, "Unexpected character {ch:?}"),
+            LexError::UnterminatedString { .. } => write!(f, "Unterminated string"),
+            LexError::InconsistentDedent { .. } => {
+                write!(f, "Dedent does not match any outer indentation level")
+            }
+            LexError::MixedIndentation { .. } => {
+                write!(f, "Inconsistent use of tabs and spaces in indentation")
+            }
+            LexError::NumberOverflow { .. } => write!(f, "Number literal too large"),
+            LexError::InvalidNumber { .. } => write!(f, "Invalid number literal"),
+        }
+    }
+}
+
+impl std::error::Error for LexError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub expected: Vec<TokenType>,
+    pub found: Token,
+    pub span: Span,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, found {}", self.message, self.found.token_type)
+    }
+}
+
+impl std::error::Error for ParseError {}
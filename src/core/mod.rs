@@ -1,2 +1,7 @@
 pub mod ast;
+pub mod bigint;
+pub mod error;
 pub mod token;
+
+#[cfg(test)]
+mod bigint_tests;
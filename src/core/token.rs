@@ -1,8 +1,10 @@
 use std::{collections::HashMap, sync::LazyLock};
 
+use crate::core::bigint::BigInt;
+
 pub type Span = (usize, usize);
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Token {
     pub token_type: TokenType,
     pub literal: Option<LiteralValue>,
@@ -22,8 +24,9 @@ impl Token {
 #[derive(Clone, Debug, PartialEq)]
 pub enum LiteralValue {
     Identifier(String),
-    String(String),
+    String(Vec<u8>),
     Int(i64),
+    Long(BigInt),
     Float(f64),
 }
 
@@ -41,8 +44,11 @@ pub enum TokenType {
     Equal,        // =
     EqualEqual,   // ==
     NotEqual,     // !=
+    LessGreater,  // <>
     LessEqual,    // <=
     GreaterEqual, // >=
+    LeftShift,    // <<
+    RightShift,   // >>
     Ampersand,    // &
     Pipe,         // |
     Caret,        // ^
@@ -59,12 +65,12 @@ pub enum TokenType {
     Colon,     // :
     Dot,       // .
     Semicolon, // ;
-    Backslash, // \
 
     // Literals
     Identifier,
     String,
     Int,
+    Long,
     Float,
 
     // Keywords
@@ -90,6 +96,7 @@ pub enum TokenType {
     Del,
     Try,
     Except,
+    Finally,
     Raise,
     Is,
     Lambda,
@@ -116,8 +123,11 @@ impl std::fmt::Display for TokenType {
             TokenType::Equal => "=",
             TokenType::EqualEqual => "==",
             TokenType::NotEqual => "!=",
+            TokenType::LessGreater => "<>",
             TokenType::LessEqual => "<=",
             TokenType::GreaterEqual => ">=",
+            TokenType::LeftShift => "<<",
+            TokenType::RightShift => ">>",
             TokenType::Ampersand => "&",
             TokenType::Pipe => "|",
             TokenType::Caret => "^",
@@ -134,12 +144,12 @@ impl std::fmt::Display for TokenType {
             TokenType::Colon => ":",
             TokenType::Dot => ".",
             TokenType::Semicolon => ";",
-            TokenType::Backslash => "\\",
 
             // Literals
             TokenType::Identifier => "identifier",
             TokenType::String => "string",
             TokenType::Int => "int",
+            TokenType::Long => "long",
             TokenType::Float => "float",
 
             // Keywords
@@ -165,6 +175,7 @@ impl std::fmt::Display for TokenType {
             TokenType::Del => "del",
             TokenType::Try => "try",
             TokenType::Except => "except",
+            TokenType::Finally => "finally",
             TokenType::Raise => "raise",
             TokenType::Is => "is",
             TokenType::Lambda => "lambda",
@@ -209,6 +220,7 @@ static KEYWORDS: LazyLock<HashMap<&'static str, TokenType>> = LazyLock::new(|| {
         ("del", TokenType::Del),
         ("try", TokenType::Try),
         ("except", TokenType::Except),
+        ("finally", TokenType::Finally),
         ("raise", TokenType::Raise),
         ("is", TokenType::Is),
         ("lambda", TokenType::Lambda),
@@ -0,0 +1,110 @@
+use crate::{
+    core::{
+        error::{LexError, ParseError},
+        token::{Token, TokenType},
+    },
+    diagnostics::{render::Diagnostic, source::SourceFile},
+};
+
+#[test]
+fn test_location_of_offsets() {
+    let source = SourceFile::new("test.py", "a = 1\nbb = 2\n\nc = 3");
+
+    assert_eq!(source.location(0), (1, 1));
+    assert_eq!(source.location(4), (1, 5));
+    assert_eq!(source.location(5), (1, 6));
+    assert_eq!(source.location(6), (2, 1));
+    assert_eq!(source.location(13), (3, 1));
+    assert_eq!(source.location(18), (4, 5));
+    assert_eq!(source.location(100), (4, 6));
+}
+
+#[test]
+fn test_lines() {
+    let source = SourceFile::new("test.py", "first\r\nsecond\n\nlast");
+
+    assert_eq!(source.line(1), "first");
+    assert_eq!(source.line(2), "second");
+    assert_eq!(source.line(3), "");
+    assert_eq!(source.line(4), "last");
+    assert_eq!(source.line(5), "");
+}
+
+#[test]
+fn test_columns_count_characters() {
+    let source = SourceFile::new("test.py", "s = 'héllo' + x");
+
+    assert_eq!(source.location(15), (1, 15));
+}
+
+#[test]
+fn test_render_underlines_span() {
+    let source = SourceFile::new("test.py", "x = 1\ny = foo bar\n");
+    let diagnostic = Diagnostic::error("Unexpected name", (14, 17)).with_note("did you mean `,`?");
+
+    assert_eq!(
+        diagnostic.render(&source),
+        "error: Unexpected name\n --> test.py:2:9\n  |\n2 | y = foo bar\n  |         ^^^\n  = note: did you mean `,`?\n"
+    );
+}
+
+#[test]
+fn test_render_empty_span_and_wide_gutter() {
+    let src = format!("{}if x\n", "\n".repeat(11));
+    let source = SourceFile::new("test.py", &src);
+    let diagnostic = Diagnostic::error("Expected ':'", (15, 15));
+
+    assert_eq!(
+        diagnostic.render(&source),
+        "error: Expected ':'\n  --> test.py:12:5\n   |\n12 | if x\n   |     ^\n"
+    );
+}
+
+#[test]
+fn test_render_keeps_tabs_aligned() {
+    let source = SourceFile::new("test.py", "\tx = $\n");
+    let diagnostic = Diagnostic::from(&LexError::UnexpectedChar {
+        ch: '$',
+        span: (5, 6),
+    });
+
+    assert_eq!(
+        diagnostic.render(&source),
+        "error: Unexpected character '$'\n --> test.py:1:6\n  |\n1 | \tx = $\n  | \t    ^\n"
+    );
+}
+
+#[test]
+fn test_render_clips_multiline_span() {
+    let source = SourceFile::new("test.py", "s = 'abc\ndef'\n");
+    let diagnostic = Diagnostic::error("Bad string", (4, 13));
+
+    assert!(
+        diagnostic
+            .render(&source)
+            .ends_with("1 | s = 'abc\n  |     ^^^^\n")
+    );
+}
+
+#[test]
+fn test_parse_error_notes() {
+    let err = ParseError {
+        expected: vec![TokenType::Colon],
+        found: Token::new(TokenType::Newline, None, (4, 4)),
+        span: (4, 4),
+        message: "Expected ':' after if condition".to_string(),
+    };
+    let diagnostic = Diagnostic::from(&err);
+
+    assert_eq!(diagnostic.message, "Expected ':' after if condition");
+    assert_eq!(diagnostic.notes, ["expected `:`, found `<newline>`"]);
+
+    let err = ParseError {
+        expected: vec![TokenType::Comma, TokenType::RParen],
+        ..err
+    };
+    assert_eq!(
+        Diagnostic::from(&err).notes,
+        ["expected one of `,`, `)`, found `<newline>`"]
+    );
+}
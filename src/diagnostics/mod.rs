@@ -0,0 +1,5 @@
+pub mod render;
+pub mod source;
+
+#[cfg(test)]
+mod diagnostics_tests;
@@ -0,0 +1,81 @@
+use crate::core::{
+    error::{LexError, ParseError},
+    token::Span,
+};
+
+use super::source::SourceFile;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, span: Span) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            notes: vec![],
+        }
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Renders the diagnostic rustc-style: the message, its location, the
+    /// offending source line with the span underlined, then any notes.
+    pub fn render(&self, source: &SourceFile) -> String {
+        let (line, column) = source.location(self.span.0);
+        let text = source.line(line);
+        let gutter = " ".repeat(line.to_string().len());
+
+        let start = source.clamp(self.span.0);
+        let end = source
+            .clamp(self.span.1)
+            .clamp(start, source.line_start(line) + text.len());
+        let width = source.text[start..end].chars().count().max(1);
+        let padding: String = text
+            .chars()
+            .take(column - 1)
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        let mut out = format!("error: {}\n", self.message);
+        out.push_str(&format!("{gutter}--> {}:{line}:{column}\n", source.name));
+        out.push_str(&format!("{gutter} |\n"));
+        out.push_str(&format!("{line} | {text}\n"));
+        out.push_str(&format!("{gutter} | {padding}{}\n", "^".repeat(width)));
+
+        for note in &self.notes {
+            out.push_str(&format!("{gutter} = note: {note}\n"));
+        }
+
+        out
+    }
+}
+
+impl From<&LexError> for Diagnostic {
+    fn from(err: &LexError) -> Self {
+        Diagnostic::error(err.to_string(), err.span())
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(err: &ParseError) -> Self {
+        let found = format!("found `{}`", err.found.token_type);
+        let note = match err.expected.as_slice() {
+            [] => found,
+            [expected] => format!("expected `{expected}`, {found}"),
+            expected => {
+                let expected: Vec<_> = expected.iter().map(|t| format!("`{t}`")).collect();
+                format!("expected one of {}, {found}", expected.join(", "))
+            }
+        };
+
+        Diagnostic::error(err.message.clone(), err.span).with_note(note)
+    }
+}
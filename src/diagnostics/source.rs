@@ -0,0 +1,58 @@
+/// A named source text with an index of line starts, used to turn byte
+/// offsets from spans into 1-based line and column numbers.
+pub struct SourceFile {
+    pub name: String,
+    pub text: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(name: &str, text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            text: text.to_string(),
+            line_starts,
+        }
+    }
+
+    pub fn line_index(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset) - 1
+    }
+
+    pub fn location(&self, offset: usize) -> (usize, usize) {
+        let offset = self.clamp(offset);
+        let index = self.line_index(offset);
+        let start = self.line_starts[index];
+        let column = self.text[start..offset].chars().count() + 1;
+
+        (index + 1, column)
+    }
+
+    pub fn clamp(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    pub fn line_start(&self, number: usize) -> usize {
+        self.line_starts
+            .get(number.saturating_sub(1))
+            .map_or(self.text.len(), |&start| start)
+    }
+
+    pub fn line(&self, number: usize) -> &str {
+        let start = self.line_start(number);
+        let end = self
+            .line_starts
+            .get(number)
+            .map_or(self.text.len(), |&end| end);
+
+        self.text[start..end.max(start)].trim_end_matches(['\n', '\r'])
+    }
+}
@@ -1,11 +1,36 @@
-use crate::core::token::{LiteralValue, Token, TokenType};
+use std::{cmp::Ordering, mem::take};
+
+use crate::core::{
+    bigint::BigInt,
+    error::LexError,
+    token::{LiteralValue, Token, TokenType},
+};
+
+const TAB_SIZE: usize = 8;
+
+/// How tabs in indentation are measured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TabPolicy {
+    /// Tabs advance to the next multiple of 8 columns, as in Python.
+    #[default]
+    Expand,
+    /// Tabs expand as usual, but indentation whose meaning would change with
+    /// a different tab width is rejected.
+    Strict,
+}
 
 pub struct Lexer<'lx> {
     src: &'lx String,
+    // Byte offsets into `src`, always on character boundaries.
     start: usize,
     current: usize,
-    indent_stack: Vec<usize>,
-    pub tokens: Vec<Token>,
+    tab_policy: TabPolicy,
+    // Indentation of each open block, as measured by `handle_indentation`.
+    indent_stack: Vec<(usize, usize)>,
+    // Newlines inside brackets don't end the logical line.
+    bracket_depth: usize,
+    tokens: Vec<Token>,
+    errors: Vec<LexError>,
 }
 
 impl<'lx> Lexer<'lx> {
@@ -14,22 +39,59 @@ impl<'lx> Lexer<'lx> {
             src,
             start: 0,
             current: 0,
-            indent_stack: vec![0],
+            tab_policy: TabPolicy::default(),
+            indent_stack: vec![(0, 0)],
+            bracket_depth: 0,
             tokens: vec![],
+            errors: vec![],
         }
     }
 
-    pub fn analyze(&mut self) {
+    pub fn with_tab_policy(mut self, tab_policy: TabPolicy) -> Self {
+        self.tab_policy = tab_policy;
+        self
+    }
+
+    pub fn analyze(&mut self) -> Result<Vec<Token>, Vec<LexError>> {
+        // Editors on some platforms prefix UTF-8 files with a byte order mark.
+        if self.current == 0 && self.src.starts_with('\u{feff}') {
+            self.current = '\u{feff}'.len_utf8();
+        }
+
         while !self.at_end() {
             self.start = self.current;
             self.lex();
         }
 
+        // Close the last logical line and any blocks still open, so a file
+        // without a trailing newline reads the same as one with it.
+        if self.in_logical_line() {
+            self.tokens.push(Token::new(
+                TokenType::Newline,
+                None,
+                (self.current, self.current),
+            ));
+        }
+        while self.indent_stack.len() > 1 {
+            self.indent_stack.pop();
+            self.tokens.push(Token::new(
+                TokenType::Dedent,
+                None,
+                (self.current, self.current),
+            ));
+        }
+
         self.tokens.push(Token::new(
             TokenType::Eof,
             None,
             (self.current, self.current),
         ));
+
+        if self.errors.is_empty() {
+            Ok(take(&mut self.tokens))
+        } else {
+            Err(take(&mut self.errors))
+        }
     }
 
     fn at_end(&self) -> bool {
@@ -48,16 +110,24 @@ impl<'lx> Lexer<'lx> {
                 self.add_token(token_type, None);
             }
             '<' => {
-                let token_type = match self.match_advance('=') {
-                    true => TokenType::LessEqual,
-                    false => TokenType::Less,
+                let token_type = if self.match_advance('=') {
+                    TokenType::LessEqual
+                } else if self.match_advance('>') {
+                    TokenType::LessGreater
+                } else if self.match_advance('<') {
+                    TokenType::LeftShift
+                } else {
+                    TokenType::Less
                 };
                 self.add_token(token_type, None);
             }
             '>' => {
-                let token_type = match self.match_advance('=') {
-                    true => TokenType::GreaterEqual,
-                    false => TokenType::Greater,
+                let token_type = if self.match_advance('=') {
+                    TokenType::GreaterEqual
+                } else if self.match_advance('>') {
+                    TokenType::RightShift
+                } else {
+                    TokenType::Greater
                 };
                 self.add_token(token_type, None);
             }
@@ -83,105 +153,199 @@ impl<'lx> Lexer<'lx> {
             '|' => self.add_token(TokenType::Pipe, None),
             '^' => self.add_token(TokenType::Caret, None),
             '~' => self.add_token(TokenType::Tilde, None),
-            '(' => self.add_token(TokenType::LParen, None),
-            ')' => self.add_token(TokenType::RParen, None),
-            '[' => self.add_token(TokenType::LBracket, None),
-            ']' => self.add_token(TokenType::RBracket, None),
-            '{' => self.add_token(TokenType::LBrace, None),
-            '}' => self.add_token(TokenType::RBrace, None),
+            '(' => self.open_bracket(TokenType::LParen),
+            ')' => self.close_bracket(TokenType::RParen),
+            '[' => self.open_bracket(TokenType::LBracket),
+            ']' => self.close_bracket(TokenType::RBracket),
+            '{' => self.open_bracket(TokenType::LBrace),
+            '}' => self.close_bracket(TokenType::RBrace),
             ',' => self.add_token(TokenType::Comma, None),
             ':' => self.add_token(TokenType::Colon, None),
+            '.' if self.peek().is_ascii_digit() => self.handle_number(ch),
             '.' => self.add_token(TokenType::Dot, None),
             ';' => self.add_token(TokenType::Semicolon, None),
-            '\\' => self.add_token(TokenType::Backslash, None),
+            '\\' if self.peek() == '\n' => {
+                self.advance();
+            }
+            '\\' if self.peek() == '\r' && self.peek_next() == '\n' => {
+                self.current += 2;
+            }
             '#' => {
                 while !matches!(self.peek(), '\n' | '\0') {
                     self.advance();
                 }
             }
             '"' | '\'' => self.handle_string(ch),
+            '\n' if self.bracket_depth > 0 => {}
             '\n' => {
-                self.tokens.push(Token::new(
-                    TokenType::Newline,
-                    None,
-                    (self.start, self.start),
-                ));
+                if self.in_logical_line() {
+                    self.tokens.push(Token::new(
+                        TokenType::Newline,
+                        None,
+                        (self.start, self.start),
+                    ));
+                }
                 self.handle_indentation();
             }
             ' ' | '\t' | '\r' => {}
             _ => {
                 if ch.is_ascii_digit() {
-                    self.handle_number();
+                    self.handle_number(ch);
                 } else if ch.is_ascii_alphabetic() || ch == '_' {
                     self.handle_identifier();
                 } else {
-                    eprintln!("Unexpected character at {} -> {:#?}", self.start, ch);
+                    self.errors.push(LexError::UnexpectedChar {
+                        ch,
+                        span: (self.start, self.current),
+                    });
                 }
             }
         }
     }
 
-    fn handle_number(&mut self) {
-        while self.peek().is_ascii_digit() {
+    // Blank and comment-only lines never start a logical line, so they emit no
+    // `Newline` of their own.
+    fn in_logical_line(&self) -> bool {
+        self.tokens.last().is_some_and(|token| {
+            !matches!(
+                token.token_type,
+                TokenType::Newline | TokenType::Indent | TokenType::Dedent
+            )
+        })
+    }
+
+    fn open_bracket(&mut self, token_type: TokenType) {
+        self.bracket_depth += 1;
+        self.add_token(token_type, None);
+    }
+
+    fn close_bracket(&mut self, token_type: TokenType) {
+        self.bracket_depth = self.bracket_depth.saturating_sub(1);
+        self.add_token(token_type, None);
+    }
+
+    fn handle_number(&mut self, first: char) {
+        if first == '0' && matches!(self.peek(), 'x' | 'X') && self.peek_next().is_ascii_hexdigit()
+        {
             self.advance();
+            while self.peek().is_ascii_hexdigit() {
+                self.advance();
+            }
+            return self.add_integer(self.start + 2, 16);
         }
 
-        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
+        let mut is_float = first == '.';
+        while self.peek().is_ascii_digit() {
             self.advance();
+        }
 
+        if !is_float && self.peek() == '.' {
+            is_float = true;
+            self.advance();
             while self.peek().is_ascii_digit() {
                 self.advance();
             }
+        }
 
-            self.add_token(
-                TokenType::Float,
-                Some(LiteralValue::Float(
-                    self.src.chars().as_str()[self.start..self.current]
-                        .parse::<f64>()
-                        .expect("Error while parsing number..."),
-                )),
-            );
+        if matches!(self.peek(), 'e' | 'E') {
+            let mut rest = self.src[self.current..].chars().skip(1);
+            let has_exponent = match rest.next() {
+                Some('+' | '-') => rest.next().is_some_and(|ch| ch.is_ascii_digit()),
+                Some(ch) => ch.is_ascii_digit(),
+                None => false,
+            };
+
+            if has_exponent {
+                is_float = true;
+                self.advance();
+                if matches!(self.peek(), '+' | '-') {
+                    self.advance();
+                }
+                while self.peek().is_ascii_digit() {
+                    self.advance();
+                }
+            }
+        }
+
+        if is_float {
+            let value = self.src.as_str()[self.start..self.current].parse::<f64>();
+            match value {
+                Ok(value) if value.is_finite() => {
+                    self.add_token(TokenType::Float, Some(LiteralValue::Float(value)))
+                }
+                _ => self.errors.push(LexError::NumberOverflow {
+                    span: (self.start, self.current),
+                }),
+            }
+        } else if first == '0' && self.current - self.start > 1 {
+            self.add_integer(self.start + 1, 8);
         } else {
-            self.add_token(
-                TokenType::Int,
-                Some(LiteralValue::Int(
-                    self.src.chars().as_str()[self.start..self.current]
-                        .parse::<i64>()
-                        .expect("Error while parsing number..."),
-                )),
-            );
+            self.add_integer(self.start, 10);
+        }
+    }
+
+    // Integers that don't fit in an `i64`, or carry an `L` suffix, are longs.
+    fn add_integer(&mut self, digits_start: usize, radix: u32) {
+        let digits = &self.src.as_str()[digits_start..self.current];
+        let long = matches!(self.peek(), 'l' | 'L');
+        if long {
+            self.advance();
+        }
+
+        let Some(value) = BigInt::from_str_radix(digits, radix) else {
+            self.errors.push(LexError::InvalidNumber {
+                span: (self.start, self.current),
+            });
+            return;
+        };
+
+        match value.to_i64() {
+            Some(value) if !long => self.add_token(TokenType::Int, Some(LiteralValue::Int(value))),
+            _ => self.add_token(TokenType::Long, Some(LiteralValue::Long(value))),
         }
     }
 
     fn handle_indentation(&mut self) {
+        let line_start = self.current;
+        // Columns with tabs advancing to the next multiple of 8, and with
+        // tabs counted as a single column.
         let mut indent = 0;
+        let mut alt_indent = 0;
 
         while !matches!(self.peek(), '\n' | '\0') {
             match self.peek() {
                 ' ' => {
-                    self.advance();
                     indent += 1;
+                    alt_indent += 1;
                 }
                 '\t' => {
-                    self.advance();
-                    indent += 4;
-                }
-                '\r' => {
-                    self.advance();
+                    indent = (indent / TAB_SIZE + 1) * TAB_SIZE;
+                    alt_indent += 1;
                 }
+                '\r' => {}
                 _ => break,
             }
+            self.advance();
+        }
+
+        // Blank and comment-only lines don't affect indentation.
+        if matches!(self.peek(), '\n' | '#' | '\0') {
+            self.start = self.current;
+            return;
         }
 
-        let current_indent = *self.indent_stack.last().unwrap();
+        let (current_indent, current_alt) = *self.indent_stack.last().unwrap();
 
-        match indent.cmp(&current_indent) {
-            std::cmp::Ordering::Greater => {
-                self.indent_stack.push(indent);
+        // Indentation is consistent when the comparison against the enclosing
+        // block comes out the same whatever width a tab is taken to be.
+        let consistent = match indent.cmp(&current_indent) {
+            Ordering::Greater => {
+                self.indent_stack.push((indent, alt_indent));
                 self.add_token(TokenType::Indent, None);
+                alt_indent > current_alt
             }
-            std::cmp::Ordering::Less => {
-                while let Some(&top) = self.indent_stack.last() {
+            Ordering::Less => {
+                while let Some(&(top, _)) = self.indent_stack.last() {
                     if indent < top {
                         self.indent_stack.pop();
                         self.add_token(TokenType::Dedent, None);
@@ -189,53 +353,114 @@ impl<'lx> Lexer<'lx> {
                         break;
                     }
                 }
-                if *self.indent_stack.last().unwrap() != indent {
-                    panic!("Inconsistent indentation at {}", self.start);
+                let (top, top_alt) = *self.indent_stack.last().unwrap();
+                if top != indent {
+                    self.errors.push(LexError::InconsistentDedent {
+                        span: (line_start, self.current),
+                    });
                 }
+                top != indent || top_alt == alt_indent
             }
-            std::cmp::Ordering::Equal => {}
+            Ordering::Equal => alt_indent == current_alt,
+        };
+
+        if !consistent && self.tab_policy == TabPolicy::Strict {
+            self.errors.push(LexError::MixedIndentation {
+                span: (line_start, self.current),
+            });
         }
 
         self.start = self.current;
     }
 
     fn handle_string(&mut self, str_char: char) {
-        let mut value = String::new();
+        let triple = self.peek() == str_char && self.peek_next() == str_char;
+        let quotes = if triple { 3 } else { 1 };
+        let closing: String = std::iter::repeat_n(str_char, quotes).collect();
+        self.current += closing.len() - str_char.len_utf8();
+
+        let mut value = Vec::new();
+
+        while !self.src[self.current..].starts_with(&closing) && !self.at_end() {
+            if self.peek() == '\n' && !triple {
+                break;
+            }
 
-        while self.peek() != str_char && !self.at_end() {
             let ch = self.advance();
 
             if ch == '\\' {
-                let escaped = match self.advance() {
-                    'n' => '\n',
-                    't' => '\t',
-                    'r' => '\r',
-                    '\\' => '\\',
-                    q if q == str_char => str_char,
-                    other => {
-                        eprintln!("Unknown escape sequence: \\{other}");
-                        other
-                    }
-                };
-                value.push(escaped);
+                self.handle_escape(&mut value);
             } else {
-                if ch == '\n' {
-                    eprintln!("Unterminated string at line {}", self.start);
-                    return;
-                }
-                value.push(ch);
+                // Non-ASCII source text contributes its UTF-8 bytes.
+                let mut buf = [0; 4];
+                value.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
             }
         }
 
-        if self.at_end() || self.peek() != str_char {
-            eprintln!("Unterminated string at {}", self.start);
+        if !self.src[self.current..].starts_with(&closing) {
+            self.errors.push(LexError::UnterminatedString {
+                span: (self.start, self.current),
+            });
             return;
         }
 
-        self.advance();
+        self.current += closing.len();
         self.add_token(TokenType::String, Some(LiteralValue::String(value)));
     }
 
+    // Strings are byte strings, so numeric escapes produce their value
+    // truncated to a byte.
+    fn handle_escape(&mut self, value: &mut Vec<u8>) {
+        let escaped = match self.peek() {
+            '\n' => None,
+            '\r' if self.peek_next() == '\n' => {
+                self.advance();
+                None
+            }
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            'a' => Some('\x07'),
+            'b' => Some('\x08'),
+            'f' => Some('\x0c'),
+            'v' => Some('\x0b'),
+            '\\' | '\'' | '"' => Some(self.peek()),
+            '0'..='7' => {
+                let mut code = 0;
+                for _ in 0..3 {
+                    match self.peek().to_digit(8) {
+                        Some(digit) => code = code * 8 + digit,
+                        None => break,
+                    }
+                    self.advance();
+                }
+                value.push(code as u8);
+                return;
+            }
+            'x' if self.peek_next().is_ascii_hexdigit() => {
+                self.advance();
+                let mut code = 0;
+                while let Some(digit) = self.peek().to_digit(16) {
+                    code = (code * 16 + digit) & 0xff;
+                    self.advance();
+                }
+                value.push(code as u8);
+                return;
+            }
+            _ => {
+                // Unknown escapes, and `\x` without hex digits, are kept
+                // verbatim, backslash included.
+                value.push(b'\\');
+                return;
+            }
+        };
+
+        self.advance();
+        if let Some(ch) = escaped {
+            value.push(ch as u8);
+        }
+    }
+
     fn handle_identifier(&mut self) {
         while self.peek().is_ascii_alphanumeric() || self.peek() == '_' {
             self.advance();
@@ -245,7 +470,7 @@ impl<'lx> Lexer<'lx> {
 
         if let Some(token_type) = TokenType::get_keyword(value) {
             match token_type {
-                TokenType::String => LiteralValue::String(value.to_string()),
+                TokenType::String => LiteralValue::String(value.into()),
                 TokenType::Int => LiteralValue::Int(value.parse::<i64>().unwrap_or_default()),
                 TokenType::Float => LiteralValue::Float(value.parse::<f64>().unwrap_or_default()),
                 _ => LiteralValue::Identifier(value.to_string()),
@@ -261,32 +486,20 @@ impl<'lx> Lexer<'lx> {
     }
 
     fn advance(&mut self) -> char {
-        let ch = self
-            .src
-            .chars()
-            .nth(self.current)
-            .expect("Error while peeking in advance()...");
-        self.current += 1;
+        let ch = self.peek();
+        if !self.at_end() {
+            self.current += ch.len_utf8();
+        }
 
         ch
     }
 
     fn match_advance(&mut self, expected: char) -> bool {
-        if self.at_end() {
-            return false;
-        }
-
-        if self
-            .src
-            .chars()
-            .nth(self.current)
-            .expect("Error while peeking in match_advance()...")
-            != expected
-        {
+        if self.at_end() || self.peek() != expected {
             return false;
         }
 
-        self.current += 1;
+        self.current += expected.len_utf8();
 
         true
     }
@@ -297,24 +510,12 @@ impl<'lx> Lexer<'lx> {
     }
 
     fn peek(&self) -> char {
-        if self.at_end() {
-            return '\0';
-        }
-
-        self.src
-            .chars()
-            .nth(self.current)
-            .expect("Error while peeking")
+        self.src[self.current..].chars().next().unwrap_or('\0')
     }
 
     fn peek_next(&self) -> char {
-        if self.current + 1 >= self.src.len() {
-            return '\0';
-        }
-
-        self.src
-            .chars()
-            .nth(self.current + 1)
-            .expect("Error while peeking")
+        let mut chars = self.src[self.current..].chars();
+        chars.next();
+        chars.next().unwrap_or('\0')
     }
 }
@@ -1,12 +1,12 @@
-use crate::core::token::*;
+use crate::core::{bigint::BigInt, error::LexError, token::*};
 
-use super::lexer::Lexer;
+use super::lexer::{Lexer, TabPolicy};
 
 #[test]
 fn test_function_definition() {
     let src = "def yo(name):\n    print(\"Yo\", name)".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
     let expected_types = [
         TokenType::Def,
@@ -23,37 +23,32 @@ fn test_function_definition() {
         TokenType::Comma,
         TokenType::Identifier,
         TokenType::RParen,
+        TokenType::Newline,
+        TokenType::Dedent,
         TokenType::Eof,
     ];
 
-    assert_eq!(
-        lexer.tokens.len(),
-        expected_types.len(),
-        "Token count mismatch"
-    );
+    assert_eq!(tokens.len(), expected_types.len(), "Token count mismatch");
 
     for (i, expected_type) in expected_types.iter().enumerate() {
         assert_eq!(
-            lexer.tokens[i].token_type, *expected_type,
+            tokens[i].token_type, *expected_type,
             "Token type mismatch at index {i}: expected {:?}, got {:?}",
-            expected_type, lexer.tokens[i].token_type
+            expected_type, tokens[i].token_type
         );
     }
 
     assert_eq!(
-        lexer.tokens[1].literal,
+        tokens[1].literal,
         Some(LiteralValue::Identifier("yo".to_string()))
     );
     assert_eq!(
-        lexer.tokens[3].literal,
+        tokens[3].literal,
         Some(LiteralValue::Identifier("name".to_string()))
     );
+    assert_eq!(tokens[10].literal, Some(LiteralValue::String("Yo".into())));
     assert_eq!(
-        lexer.tokens[10].literal,
-        Some(LiteralValue::String("Yo".to_string()))
-    );
-    assert_eq!(
-        lexer.tokens[12].literal,
+        tokens[12].literal,
         Some(LiteralValue::Identifier("name".to_string()))
     );
 }
@@ -62,7 +57,7 @@ fn test_function_definition() {
 fn test_operators() {
     let src = "+ - * / % ** < > = == != <= >= & | ^ ~".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
     let expected_types = [
         TokenType::Plus,
@@ -82,12 +77,13 @@ fn test_operators() {
         TokenType::Pipe,
         TokenType::Caret,
         TokenType::Tilde,
+        TokenType::Newline,
         TokenType::Eof,
     ];
 
     for (i, expected_type) in expected_types.iter().enumerate() {
         assert_eq!(
-            lexer.tokens[i].token_type, *expected_type,
+            tokens[i].token_type, *expected_type,
             "Operator mismatch at index {i}"
         );
     }
@@ -95,9 +91,9 @@ fn test_operators() {
 
 #[test]
 fn test_delimiters() {
-    let src = "( ) [ ] { } , : . ; \\".to_string();
+    let src = "( ) [ ] { } , : . ;".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
     let expected_types = [
         TokenType::LParen,
@@ -110,13 +106,13 @@ fn test_delimiters() {
         TokenType::Colon,
         TokenType::Dot,
         TokenType::Semicolon,
-        TokenType::Backslash,
+        TokenType::Newline,
         TokenType::Eof,
     ];
 
     for (i, expected_type) in expected_types.iter().enumerate() {
         assert_eq!(
-            lexer.tokens[i].token_type, *expected_type,
+            tokens[i].token_type, *expected_type,
             "Delimiter mismatch at index {i}"
         );
     }
@@ -126,65 +122,151 @@ fn test_delimiters() {
 fn test_integers() {
     let src = "69 0 420".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
-    assert_eq!(lexer.tokens[0].token_type, TokenType::Int);
-    assert_eq!(lexer.tokens[0].literal, Some(LiteralValue::Int(69)));
+    assert_eq!(tokens[0].token_type, TokenType::Int);
+    assert_eq!(tokens[0].literal, Some(LiteralValue::Int(69)));
 
-    assert_eq!(lexer.tokens[1].token_type, TokenType::Int);
-    assert_eq!(lexer.tokens[1].literal, Some(LiteralValue::Int(0)));
+    assert_eq!(tokens[1].token_type, TokenType::Int);
+    assert_eq!(tokens[1].literal, Some(LiteralValue::Int(0)));
 
-    assert_eq!(lexer.tokens[2].token_type, TokenType::Int);
-    assert_eq!(lexer.tokens[2].literal, Some(LiteralValue::Int(420)));
+    assert_eq!(tokens[2].token_type, TokenType::Int);
+    assert_eq!(tokens[2].literal, Some(LiteralValue::Int(420)));
 }
 
 #[test]
 fn test_floats() {
     let src = "3.41 0.5 42.0".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
-    assert_eq!(lexer.tokens[0].token_type, TokenType::Float);
-    assert_eq!(lexer.tokens[0].literal, Some(LiteralValue::Float(3.41)));
+    assert_eq!(tokens[0].token_type, TokenType::Float);
+    assert_eq!(tokens[0].literal, Some(LiteralValue::Float(3.41)));
 
-    assert_eq!(lexer.tokens[1].token_type, TokenType::Float);
-    assert_eq!(lexer.tokens[1].literal, Some(LiteralValue::Float(0.5)));
+    assert_eq!(tokens[1].token_type, TokenType::Float);
+    assert_eq!(tokens[1].literal, Some(LiteralValue::Float(0.5)));
 
-    assert_eq!(lexer.tokens[2].token_type, TokenType::Float);
-    assert_eq!(lexer.tokens[2].literal, Some(LiteralValue::Float(42.0)));
+    assert_eq!(tokens[2].token_type, TokenType::Float);
+    assert_eq!(tokens[2].literal, Some(LiteralValue::Float(42.0)));
 }
 
 #[test]
-fn test_strings() {
-    let src = r#""yo" 'gurt' "gurt\nyo""#.to_string();
+fn test_hex_and_octal_integers() {
+    let src = "0x1F 0XfF 017 00".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let literals = tokens[..4]
+        .iter()
+        .map(|token| token.literal.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        literals,
+        vec![
+            Some(LiteralValue::Int(31)),
+            Some(LiteralValue::Int(255)),
+            Some(LiteralValue::Int(15)),
+            Some(LiteralValue::Int(0)),
+        ]
+    );
+}
+
+#[test]
+fn test_long_integers() {
+    let src = "123L 0x10l 99999999999999999999".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(tokens[0].token_type, TokenType::Long);
+    assert_eq!(
+        tokens[0].literal,
+        Some(LiteralValue::Long(BigInt::from_i64(123)))
+    );
+    assert_eq!(tokens[0].span, (0, 4));
+
+    assert_eq!(tokens[1].token_type, TokenType::Long);
+    assert_eq!(
+        tokens[1].literal,
+        Some(LiteralValue::Long(BigInt::from_i64(16)))
+    );
+
+    assert_eq!(tokens[2].token_type, TokenType::Long);
+    assert_eq!(
+        tokens[2].literal,
+        Some(LiteralValue::Long(
+            BigInt::from_str_radix("99999999999999999999", 10).unwrap()
+        ))
+    );
+}
+
+#[test]
+fn test_exponent_and_leading_dot_floats() {
+    let src = "1e10 2.5E-3 .5 1. 3e+2".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
-    assert_eq!(lexer.tokens[0].token_type, TokenType::String);
+    let literals = tokens[..5]
+        .iter()
+        .map(|token| token.literal.clone())
+        .collect::<Vec<_>>();
     assert_eq!(
-        lexer.tokens[0].literal,
-        Some(LiteralValue::String("yo".to_string()))
+        literals,
+        vec![
+            Some(LiteralValue::Float(1e10)),
+            Some(LiteralValue::Float(2.5e-3)),
+            Some(LiteralValue::Float(0.5)),
+            Some(LiteralValue::Float(1.0)),
+            Some(LiteralValue::Float(300.0)),
+        ]
     );
+}
+
+#[test]
+fn test_exponent_requires_digits() {
+    let src = "1else".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(tokens[0].literal, Some(LiteralValue::Int(1)));
+    assert_eq!(tokens[1].token_type, TokenType::Else);
+}
+
+#[test]
+fn test_invalid_octal_digit() {
+    let src = "n = 089".to_string();
+    let mut lexer = Lexer::new(&src);
 
-    assert_eq!(lexer.tokens[1].token_type, TokenType::String);
     assert_eq!(
-        lexer.tokens[1].literal,
-        Some(LiteralValue::String("gurt".to_string()))
+        lexer.analyze(),
+        Err(vec![LexError::InvalidNumber { span: (4, 7) }])
     );
+}
+
+#[test]
+fn test_strings() {
+    let src = r#""yo" 'gurt' "gurt\nyo""#.to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(tokens[0].token_type, TokenType::String);
+    assert_eq!(tokens[0].literal, Some(LiteralValue::String("yo".into())));
 
-    assert_eq!(lexer.tokens[2].token_type, TokenType::String);
+    assert_eq!(tokens[1].token_type, TokenType::String);
+    assert_eq!(tokens[1].literal, Some(LiteralValue::String("gurt".into())));
+
+    assert_eq!(tokens[2].token_type, TokenType::String);
     assert_eq!(
-        lexer.tokens[2].literal,
-        Some(LiteralValue::String("gurt\nyo".to_string()))
+        tokens[2].literal,
+        Some(LiteralValue::String("gurt\nyo".into()))
     );
 }
 
 #[test]
 fn test_keywords() {
     let src =
-            "and or not if elif else while for in break continue return def class pass import from print global del try except raise is lambda".to_string();
+            "and or not if elif else while for in break continue return def class pass import from print global del try except finally raise is lambda".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
     let expected_types = [
         TokenType::And,
@@ -209,6 +291,7 @@ fn test_keywords() {
         TokenType::Del,
         TokenType::Try,
         TokenType::Except,
+        TokenType::Finally,
         TokenType::Raise,
         TokenType::Is,
         TokenType::Lambda,
@@ -216,7 +299,7 @@ fn test_keywords() {
 
     for (i, expected_type) in expected_types.iter().enumerate() {
         assert_eq!(
-            lexer.tokens[i].token_type, *expected_type,
+            tokens[i].token_type, *expected_type,
             "Keyword mismatch at index {i}"
         );
     }
@@ -226,14 +309,14 @@ fn test_keywords() {
 fn test_identifiers() {
     let src = "variable_name func123 _private CamelCase".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
     let expected_identifiers = ["variable_name", "func123", "_private", "CamelCase"];
 
     for (i, expected_id) in expected_identifiers.iter().enumerate() {
-        assert_eq!(lexer.tokens[i].token_type, TokenType::Identifier);
+        assert_eq!(tokens[i].token_type, TokenType::Identifier);
         assert_eq!(
-            lexer.tokens[i].literal,
+            tokens[i].literal,
             Some(LiteralValue::Identifier(expected_id.to_string()))
         );
     }
@@ -245,10 +328,9 @@ fn test_nested_indentation() {
         "if True:\n    if nested:\n        print(\"deep\")\n    print(\"back\")\nprint(\"root\")"
             .to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
-    let indent_tokens: Vec<_> = lexer
-        .tokens
+    let indent_tokens: Vec<_> = tokens
         .iter()
         .filter(|t| matches!(t.token_type, TokenType::Indent | TokenType::Dedent))
         .collect();
@@ -264,10 +346,9 @@ fn test_nested_indentation() {
 fn test_comments() {
     let src = "x = 5  # dababy yo\ny = 10".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
-    let non_newline_tokens: Vec<_> = lexer
-        .tokens
+    let non_newline_tokens: Vec<_> = tokens
         .iter()
         .filter(|t| !matches!(t.token_type, TokenType::Newline))
         .collect();
@@ -282,22 +363,21 @@ fn test_comments() {
 fn test_empty_lines() {
     let src = "x = 1\n\n\ny = 2".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
-    let newline_count = lexer
-        .tokens
+    let newline_count = tokens
         .iter()
         .filter(|t| matches!(t.token_type, TokenType::Newline))
         .count();
 
-    assert_eq!(newline_count, 3);
+    assert_eq!(newline_count, 2);
 }
 
 #[test]
 fn test_complex_expression() {
     let src = "bruh = (a + b) * 2.5".to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
 
     let expected_types = [
         TokenType::Identifier,
@@ -309,12 +389,13 @@ fn test_complex_expression() {
         TokenType::RParen,
         TokenType::Star,
         TokenType::Float,
+        TokenType::Newline,
         TokenType::Eof,
     ];
 
     for (i, expected_type) in expected_types.iter().enumerate() {
         assert_eq!(
-            lexer.tokens[i].token_type, *expected_type,
+            tokens[i].token_type, *expected_type,
             "Complex expression mismatch at index {i}"
         );
     }
@@ -324,11 +405,464 @@ fn test_complex_expression() {
 fn test_string_escapes() {
     let src = r#""yo\ngurt\t\"\\\r""#.to_string();
     let mut lexer = Lexer::new(&src);
-    lexer.analyze();
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(tokens[0].token_type, TokenType::String);
+    assert_eq!(
+        tokens[0].literal,
+        Some(LiteralValue::String("yo\ngurt\t\"\\\r".into()))
+    );
+}
+
+#[test]
+fn test_unexpected_character() {
+    let src = "x = 1 $ 2".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::UnexpectedChar {
+            ch: '$',
+            span: (6, 7)
+        }])
+    );
+}
+
+#[test]
+fn test_unterminated_string() {
+    let src = "s = 'abc\nt = 1".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::UnterminatedString { span: (4, 8) }])
+    );
+}
+
+#[test]
+fn test_hex_escape_without_digits_is_kept_verbatim() {
+    let src = r#""a\xg\x""#.to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(
+        tokens[0].literal,
+        Some(LiteralValue::String(r"a\xg\x".into()))
+    );
+}
+
+#[test]
+fn test_unknown_escapes_are_kept_verbatim() {
+    let src = r#"'\q\d\(\\'"#.to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(
+        tokens[0].literal,
+        Some(LiteralValue::String(r"\q\d\(\".into()))
+    );
+}
+
+#[test]
+fn test_numeric_and_control_escapes() {
+    let src = r#""\a\b\f\v\0|\101\1010|\x41\x7e|\x1234|\377\'\"""#.to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(
+        tokens[0].literal,
+        Some(LiteralValue::String(
+            b"\x07\x08\x0c\x0b\0|AA0|A~|4|\xff'\"".to_vec()
+        ))
+    );
+}
+
+#[test]
+fn test_backslash_newline_in_string() {
+    let src = "s = 'one \\\ntwo \\\r\nthree'\n".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(
+        tokens[2].literal,
+        Some(LiteralValue::String("one two three".into()))
+    );
+    assert_eq!(tokens[3].token_type, TokenType::Newline);
+}
+
+#[test]
+fn test_inconsistent_dedent() {
+    let src = "if x:\n        y = 1\n    z = 2\n".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::InconsistentDedent { span: (20, 24) }])
+    );
+}
+
+#[test]
+fn test_number_overflow() {
+    let src = "n = 1e400".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::NumberOverflow { span: (4, 9) }])
+    );
+}
+
+#[test]
+fn test_errors_are_collected() {
+    let src = "a = $\nb = 'open\nc = ?".to_string();
+    let mut lexer = Lexer::new(&src);
+    let errors = lexer.analyze().unwrap_err();
+
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(
+        errors[0],
+        LexError::UnexpectedChar { ch: '$', .. }
+    ));
+    assert!(matches!(errors[1], LexError::UnterminatedString { .. }));
+    assert!(matches!(
+        errors[2],
+        LexError::UnexpectedChar { ch: '?', .. }
+    ));
+}
+
+fn generate_source(functions: usize) -> String {
+    (0..functions)
+        .map(|i| {
+            format!(
+                "def f{i}(a, b):\n    # comment {i}\n    x = a + b * {i}.5\n    return \"str{i}\", x\n\n"
+            )
+        })
+        .collect()
+}
+
+/// Benchmark: run with `cargo test --release lexer_scales_linearly -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn test_lexer_scales_linearly() {
+    use std::time::{Duration, Instant};
+
+    let time = |functions: usize| {
+        let src = generate_source(functions);
+        (0..5)
+            .map(|_| {
+                let started = Instant::now();
+                Lexer::new(&src).analyze().unwrap();
+                started.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::ZERO)
+    };
+
+    let small = time(2_000);
+    let large = time(16_000);
+    let ratio = large.as_secs_f64() / small.as_secs_f64();
+    println!("10k lines: {small:?}, 80k lines: {large:?}, ratio {ratio:.1}");
+
+    // 8x the input should take roughly 8x as long; quadratic lexing is ~64x.
+    assert!(
+        ratio < 20.0,
+        "lexing scaled super-linearly: ratio {ratio:.1}"
+    );
+}
 
-    assert_eq!(lexer.tokens[0].token_type, TokenType::String);
+#[test]
+fn test_large_input_token_count() {
+    let src = generate_source(1_000);
+    let tokens = Lexer::new(&src).analyze().unwrap();
+
+    let defs = tokens
+        .iter()
+        .filter(|t| t.token_type == TokenType::Def)
+        .count();
+    assert_eq!(defs, 1_000);
+    assert_eq!(tokens.last().unwrap().span, (src.len(), src.len()));
+}
+
+#[test]
+fn test_unicode_in_strings_and_comments() {
+    let src = "s = 'héllo wörld ✓' # ünïcode comment 🐍\nt = \"日本\"\n".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(
+        tokens[2].literal,
+        Some(LiteralValue::String("héllo wörld ✓".into()))
+    );
+    assert_eq!(&src[tokens[2].span.0..tokens[2].span.1], "'héllo wörld ✓'");
+    assert_eq!(tokens[6].literal, Some(LiteralValue::String("日本".into())));
+    assert_eq!(&src[tokens[6].span.0..tokens[6].span.1], "\"日本\"");
+}
+
+#[test]
+fn test_spans_are_byte_offsets_after_unicode() {
+    let src = "x = 'ñ' + yy".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let yy = &tokens[4];
+    assert_eq!(yy.token_type, TokenType::Identifier);
+    assert_eq!(yy.span, (11, 13));
+    assert_eq!(&src[yy.span.0..yy.span.1], "yy");
+    assert_eq!(tokens.last().unwrap().span, (src.len(), src.len()));
+}
+
+#[test]
+fn test_unexpected_unicode_character() {
+    let src = "x = 1 → 2".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::UnexpectedChar {
+            ch: '→',
+            span: (6, 9)
+        }])
+    );
+}
+
+#[test]
+fn test_unterminated_unicode_string() {
+    let src = "s = 'ça\n".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::UnterminatedString { span: (4, 8) }])
+    );
+}
+
+#[test]
+fn test_byte_order_mark_is_skipped() {
+    let src = "\u{feff}x = 1\n".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(tokens[0].token_type, TokenType::Identifier);
+    assert_eq!(tokens[0].span, (3, 4));
+}
+
+#[test]
+fn test_triple_quoted_strings() {
+    let src = "d = \"\"\"Docstring with 'quotes' and \"one\" \"\"two\"\"\n    spanning lines\"\"\"\ns = '''a\\'''b'''\ne = ''\n".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    assert_eq!(
+        tokens[2].literal,
+        Some(LiteralValue::String(
+            "Docstring with 'quotes' and \"one\" \"\"two\"\"\n    spanning lines".into()
+        ))
+    );
+    assert_eq!(tokens[3].token_type, TokenType::Newline);
+    assert_eq!(
+        tokens[6].literal,
+        Some(LiteralValue::String("a'''b".into()))
+    );
+    assert_eq!(tokens[10].literal, Some(LiteralValue::String(vec![])));
+}
+
+#[test]
+fn test_unterminated_triple_quoted_string() {
+    let src = "s = '''never\nclosed''\n".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::UnterminatedString {
+            span: (4, src.len())
+        }])
+    );
+}
+
+#[test]
+fn test_newlines_inside_brackets_are_ignored() {
+    let src = "x = [1,\n     2,  # two\n\n  3]\ny = 4\n".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let types = tokens
+        .iter()
+        .map(|token| token.token_type)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::Identifier,
+            TokenType::Equal,
+            TokenType::LBracket,
+            TokenType::Int,
+            TokenType::Comma,
+            TokenType::Int,
+            TokenType::Comma,
+            TokenType::Int,
+            TokenType::RBracket,
+            TokenType::Newline,
+            TokenType::Identifier,
+            TokenType::Equal,
+            TokenType::Int,
+            TokenType::Newline,
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_backslash_continuation() {
+    let src = "if 1:\n    x = 1 + \\\r\n2\n    y = (x \\\n)".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let newlines = tokens
+        .iter()
+        .filter(|token| token.token_type == TokenType::Newline)
+        .count();
+    assert_eq!(newlines, 3);
+    let dedents = tokens
+        .iter()
+        .filter(|token| token.token_type == TokenType::Dedent)
+        .count();
+    assert_eq!(dedents, 1);
+    assert_eq!(tokens[tokens.len() - 2].token_type, TokenType::Dedent);
+}
+
+#[test]
+fn test_stray_backslash() {
+    let src = "x = 1 \\ 2".to_string();
+    let mut lexer = Lexer::new(&src);
+
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::UnexpectedChar {
+            ch: '\\',
+            span: (6, 7)
+        }])
+    );
+}
+
+#[test]
+fn test_blank_and_comment_lines_do_not_affect_indentation() {
+    let src =
+        "\n# header\nif x:\n    a = 1\n\n# flush left\n        # deeper\n    b = 2\n".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let types = tokens
+        .iter()
+        .map(|token| token.token_type)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            TokenType::If,
+            TokenType::Identifier,
+            TokenType::Colon,
+            TokenType::Newline,
+            TokenType::Indent,
+            TokenType::Identifier,
+            TokenType::Equal,
+            TokenType::Int,
+            TokenType::Newline,
+            TokenType::Identifier,
+            TokenType::Equal,
+            TokenType::Int,
+            TokenType::Newline,
+            TokenType::Dedent,
+            TokenType::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_open_blocks_are_closed_at_eof() {
+    let src = "def f():\n    if x:\n        return 1".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let tail = tokens[tokens.len() - 4..]
+        .iter()
+        .map(|token| (token.token_type, token.span))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tail,
+        vec![
+            (TokenType::Newline, (35, 35)),
+            (TokenType::Dedent, (35, 35)),
+            (TokenType::Dedent, (35, 35)),
+            (TokenType::Eof, (35, 35)),
+        ]
+    );
+}
+
+#[test]
+fn test_tabs_advance_to_multiple_of_eight() {
+    let src = "if x:\n\tif y:\n\t\ta\n\t    \tb\n        c\n".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let indentation = tokens
+        .iter()
+        .filter(|token| matches!(token.token_type, TokenType::Indent | TokenType::Dedent))
+        .map(|token| token.token_type)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        indentation,
+        vec![
+            TokenType::Indent,
+            TokenType::Indent,
+            TokenType::Dedent,
+            TokenType::Dedent,
+        ]
+    );
+}
+
+#[test]
+fn test_strict_tabs_accept_consistent_indentation() {
+    let src = "if x:\n\tif y:\n\t\ta\n\tb\nif z:\n    c\n".to_string();
+    let mut lexer = Lexer::new(&src).with_tab_policy(TabPolicy::Strict);
+
+    assert!(lexer.analyze().is_ok());
+}
+
+#[test]
+fn test_strict_tabs_reject_ambiguous_mixing() {
+    let src = "if x:\n\ta\n        b\n".to_string();
+
+    let mut lexer = Lexer::new(&src);
+    assert!(lexer.analyze().is_ok());
+
+    let mut lexer = Lexer::new(&src).with_tab_policy(TabPolicy::Strict);
+    assert_eq!(
+        lexer.analyze(),
+        Err(vec![LexError::MixedIndentation { span: (9, 17) }])
+    );
+}
+
+#[test]
+fn test_shift_and_inequality_operators() {
+    let src = "a << 2 >> b <> c <= d >= e".to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let operators = tokens
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .map(|token| token.token_type)
+        .collect::<Vec<_>>();
     assert_eq!(
-        lexer.tokens[0].literal,
-        Some(LiteralValue::String("yo\ngurt\t\"\\\r".to_string()))
+        operators,
+        vec![
+            TokenType::LeftShift,
+            TokenType::RightShift,
+            TokenType::LessGreater,
+            TokenType::LessEqual,
+            TokenType::GreaterEqual,
+            TokenType::Newline,
+        ]
     );
 }
@@ -1,11 +1,17 @@
+use std::mem::take;
+
 use crate::core::{
-    ast::{Expr, Stmt, Target},
+    ast::{CmpOp, ExceptClause, Expr, Parameters, Stmt, StmtKind, Target},
+    error::ParseError,
     token::{LiteralValue, Token, TokenType},
 };
 
+type ParseResult<T> = Result<T, ParseError>;
+
 pub struct Parser<'prs> {
     current: usize,
-    pub statements: Vec<Stmt>,
+    statements: Vec<Stmt>,
+    errors: Vec<ParseError>,
     tokens: &'prs Vec<Token>,
 }
 
@@ -14,58 +20,88 @@ impl<'prs> Parser<'prs> {
         Parser {
             current: 0,
             statements: vec![],
+            errors: vec![],
             tokens,
         }
     }
 
-    pub fn parse(&mut self) {
+    pub fn parse(&mut self) -> Result<Vec<Stmt>, Vec<ParseError>> {
         self.statements.clear();
+        self.errors.clear();
 
         while !self.is_at_end() {
-            if let Some(stmt) = self.declaration() {
-                self.statements.push(stmt);
-            } else {
-                self.advance();
+            if self.matches(&[TokenType::Newline]) {
+                continue;
             }
-        }
-    }
 
-    fn declaration(&mut self) -> Option<Stmt> {
-        if self.matches(&[TokenType::Def]) {
-            return self.function_declaration();
+            let start = self.current;
+            match self.declaration() {
+                Ok(stmt) => self.statements.push(stmt),
+                Err(err) => self.recover(err, start),
+            }
         }
 
-        if self.matches(&[TokenType::Class]) {
-            return self.class_declaration();
+        if self.errors.is_empty() {
+            Ok(take(&mut self.statements))
+        } else {
+            Err(take(&mut self.errors))
         }
+    }
 
-        if self.matches(&[TokenType::Import]) {
-            return self.import_statement();
-        }
+    fn declaration(&mut self) -> ParseResult<Stmt> {
+        let start = self.peek().span.0;
+
+        let kind = if self.matches(&[TokenType::Def]) {
+            self.function_declaration()
+        } else if self.matches(&[TokenType::Class]) {
+            self.class_declaration()
+        } else if self.matches(&[TokenType::Import]) {
+            self.import_statement()
+        } else if self.matches(&[TokenType::From]) {
+            self.import_from_statement()
+        } else {
+            self.statement()
+        }?;
 
-        if self.matches(&[TokenType::From]) {
-            return self.import_from_statement();
-        }
+        Ok(self.spanned(kind, start))
+    }
 
-        self.statement()
+    fn spanned(&self, kind: StmtKind, start: usize) -> Stmt {
+        let end = self.peek_previous().span.1.max(start);
+        Stmt::new(kind, (start, end))
     }
 
-    fn statement(&mut self) -> Option<Stmt> {
+    fn statement(&mut self) -> ParseResult<StmtKind> {
         if self.matches(&[TokenType::Del]) {
-            let target = self.parse_target_list()?;
-            self.consume(TokenType::Newline, "Expected newline after del");
-            return Some(Stmt::Del(target));
+            let mut targets = vec![self.expression()?];
+            while self.matches(&[TokenType::Comma]) {
+                targets.push(self.expression()?);
+            }
+            let target = if targets.len() == 1 {
+                targets.remove(0)
+            } else {
+                Expr::Tuple(targets)
+            };
+            let target = self.expr_to_target(target)?;
+            self.end_statement("Expected newline after del")?;
+            return Ok(StmtKind::Del(target));
         }
 
         if self.matches(&[TokenType::Raise]) {
-            let exception = if !self.check(&TokenType::Newline) {
-                Some(self.expression()?)
+            let (exception, value) = if !self.at_statement_end() {
+                let exception = self.expression()?;
+                let value = if self.matches(&[TokenType::Comma]) {
+                    Some(self.expression()?)
+                } else {
+                    None
+                };
+                (Some(exception), value)
             } else {
-                None
+                (None, None)
             };
 
-            self.consume(TokenType::Newline, "Expected newline after raise");
-            return Some(Stmt::Raise(exception));
+            self.end_statement("Expected newline after raise")?;
+            return Ok(StmtKind::Raise { exception, value });
         }
 
         if self.matches(&[TokenType::Try]) {
@@ -73,34 +109,47 @@ impl<'prs> Parser<'prs> {
         }
 
         if self.matches(&[TokenType::Return]) {
-            let expr = if !self.check(&TokenType::Newline) {
+            let expr = if !self.at_statement_end() {
                 Some(self.expression()?)
             } else {
                 None
             };
-            self.consume(TokenType::Newline, "Expected newline after return");
-            return Some(Stmt::Return(expr));
+            self.end_statement("Expected newline after return")?;
+            return Ok(StmtKind::Return(expr));
         }
 
         if self.matches(&[TokenType::Print]) {
-            let expr = self.expression()?;
-            self.consume(TokenType::Newline, "Expected newline after print");
-            return Some(Stmt::Print(expr));
+            let mut items = vec![];
+            let mut trailing_comma = false;
+
+            while !self.at_statement_end() {
+                items.push(self.expression()?);
+                trailing_comma = self.matches(&[TokenType::Comma]);
+                if !trailing_comma {
+                    break;
+                }
+            }
+
+            self.end_statement("Expected newline after print")?;
+            return Ok(StmtKind::Print {
+                items,
+                trailing_comma,
+            });
         }
 
         if self.matches(&[TokenType::Pass]) {
-            self.consume(TokenType::Newline, "Expected newline after pass");
-            return Some(Stmt::Pass);
+            self.end_statement("Expected newline after pass")?;
+            return Ok(StmtKind::Pass);
         }
 
         if self.matches(&[TokenType::Break]) {
-            self.consume(TokenType::Newline, "Expected newline after break");
-            return Some(Stmt::Break);
+            self.end_statement("Expected newline after break")?;
+            return Ok(StmtKind::Break);
         }
 
         if self.matches(&[TokenType::Continue]) {
-            self.consume(TokenType::Newline, "Expected newline after continue");
-            return Some(Stmt::Continue);
+            self.end_statement("Expected newline after continue")?;
+            return Ok(StmtKind::Continue);
         }
 
         if self.matches(&[TokenType::For]) {
@@ -119,24 +168,21 @@ impl<'prs> Parser<'prs> {
             let mut names = vec![];
 
             loop {
-                let name_token = self.consume(TokenType::Identifier, "Expected variable name")?;
-                if let Some(LiteralValue::Identifier(name)) = &name_token.literal {
-                    names.push(name.clone());
-                }
+                names.push(self.identifier("Expected variable name")?);
 
                 if !self.matches(&[TokenType::Comma]) {
                     break;
                 }
             }
 
-            self.consume(TokenType::Newline, "Expected newline after global");
-            return Some(Stmt::Global(names));
+            self.end_statement("Expected newline after global")?;
+            return Ok(StmtKind::Global(names));
         }
 
         self.assignment_or_expression()
     }
 
-    fn assignment_or_expression(&mut self) -> Option<Stmt> {
+    fn assignment_or_expression(&mut self) -> ParseResult<StmtKind> {
         let mut exprs = vec![self.expression()?];
 
         while self.matches(&[TokenType::Comma]) {
@@ -147,9 +193,9 @@ impl<'prs> Parser<'prs> {
             let targets = exprs
                 .into_iter()
                 .map(|expr| self.expr_to_target(expr))
-                .collect::<Option<Vec<_>>>()?;
+                .collect::<ParseResult<Vec<_>>>()?;
             let value = self.tuple_or_expression()?;
-            self.consume(TokenType::Newline, "Expected newline after assignment");
+            self.end_statement("Expected newline after assignment")?;
 
             let target = if targets.len() == 1 {
                 targets.into_iter().next().unwrap()
@@ -157,7 +203,7 @@ impl<'prs> Parser<'prs> {
                 Target::Tuple(targets)
             };
 
-            Some(Stmt::Assign { target, value })
+            Ok(StmtKind::Assign { target, value })
         } else {
             let expr = if exprs.len() == 1 {
                 exprs.into_iter().next().unwrap()
@@ -165,110 +211,110 @@ impl<'prs> Parser<'prs> {
                 Expr::Tuple(exprs)
             };
 
-            self.consume(TokenType::Newline, "Expected newline after expression");
-            Some(Stmt::Expression(expr))
+            self.end_statement("Expected newline after expression")?;
+            Ok(StmtKind::Expression(expr))
         }
     }
 
     #[allow(clippy::only_used_in_recursion)]
-    fn expr_to_target(&self, expr: Expr) -> Option<Target> {
+    fn expr_to_target(&self, expr: Expr) -> ParseResult<Target> {
         match expr {
-            Expr::Variable(name) => Some(Target::Name(name)),
-            Expr::Get { object, name } => Some(Target::Attribute { object, name }),
+            Expr::Variable(name) => Ok(Target::Name(name)),
+            Expr::Get { object, name } => Ok(Target::Attribute { object, name }),
+            Expr::Index { object, index } => Ok(Target::Index { object, index }),
+            Expr::Slice {
+                object,
+                lower,
+                upper,
+            } => Ok(Target::Slice {
+                object,
+                lower,
+                upper,
+            }),
             Expr::Tuple(exprs) => {
                 let targets = exprs
                     .into_iter()
                     .map(|expr| self.expr_to_target(expr))
-                    .collect::<Option<Vec<_>>>()?;
-                Some(Target::Tuple(targets))
-            }
-            _ => {
-                eprintln!("Invalid assignment target");
-                None
+                    .collect::<ParseResult<Vec<_>>>()?;
+                Ok(Target::Tuple(targets))
             }
+            _ => Err(self.error_at(self.peek_previous(), &[], "Invalid assignment target")),
         }
     }
 
-    fn try_statement(&mut self) -> Option<Stmt> {
+    fn try_statement(&mut self) -> ParseResult<StmtKind> {
         self.consume(TokenType::Colon, "Expected ':' after try")?;
-        self.consume(TokenType::Newline, "Expected newline after try ':')")?;
-        self.consume(TokenType::Indent, "Expected indent after try")?;
-
-        let mut try_body = vec![];
-        while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-            try_body.push(self.declaration()?);
-        }
-
-        self.consume(TokenType::Dedent, "Expected dedent after try block")?;
+        let try_body = self.block("try")?;
 
         let mut except_clauses = vec![];
 
         while self.matches(&[TokenType::Except]) {
-            let exception_type = if self.check(&TokenType::Identifier) {
-                Some(self.expression()?)
-            } else {
-                None
-            };
+            let mut matcher = None;
+            let mut target = None;
+
+            if !self.check(&TokenType::Colon) {
+                matcher = Some(self.expression()?);
+                if self.matches(&[TokenType::Comma]) {
+                    let expr = self.expression()?;
+                    target = Some(self.expr_to_target(expr)?);
+                }
+            }
 
             self.consume(TokenType::Colon, "Expected ':' after except")?;
-            self.consume(TokenType::Newline, "Expected newline after except ':')")?;
-            self.consume(TokenType::Indent, "Expected indent after except")?;
+            let body = self.block("except")?;
+            except_clauses.push(ExceptClause {
+                matcher,
+                target,
+                body,
+            });
+        }
 
-            let mut except_body = vec![];
-            while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-                except_body.push(self.declaration()?);
-            }
+        let finally_body = if self.matches(&[TokenType::Finally]) {
+            self.consume(TokenType::Colon, "Expected ':' after finally")?;
+            Some(self.block("finally")?)
+        } else {
+            None
+        };
 
-            self.consume(TokenType::Dedent, "Expected dedent after except block")?;
-            except_clauses.push((exception_type, except_body));
+        if except_clauses.is_empty() && finally_body.is_none() {
+            return Err(self.error(
+                &[TokenType::Except, TokenType::Finally],
+                "Expected 'except' or 'finally' after try block",
+            ));
         }
 
-        Some(Stmt::Try {
+        Ok(StmtKind::Try {
             body: try_body,
             except_clauses,
+            finally_body,
         })
     }
 
-    fn if_statement(&mut self) -> Option<Stmt> {
+    fn if_statement(&mut self) -> ParseResult<StmtKind> {
         let condition = self.expression()?;
-        self.consume(TokenType::Colon, "Expected ':' after if condition");
-        self.consume(TokenType::Newline, "Expected newline after ':'");
-        self.consume(TokenType::Indent, "Expected indent after if statement");
-
-        let mut then_branch = vec![];
-        while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-            then_branch.push(self.declaration()?);
-        }
-
-        self.consume(TokenType::Dedent, "Expected dedent after if block");
+        self.consume(TokenType::Colon, "Expected ':' after if condition")?;
+        let then_branch = self.block("if")?;
 
         let else_branch = if self.matches(&[TokenType::Elif]) {
+            let start = self.peek_previous().span.0;
             let elif_stmt = self.if_statement()?;
-            Some(vec![elif_stmt])
+            Some(vec![self.spanned(elif_stmt, start)])
         } else if self.matches(&[TokenType::Else]) {
-            self.consume(TokenType::Colon, "Expected ':' after else");
-            self.consume(TokenType::Newline, "Expected newline after else ':'");
-            self.consume(TokenType::Indent, "Expected indent after else statement");
-
-            let mut else_block = vec![];
-            while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-                else_block.push(self.declaration()?);
-            }
-
-            self.consume(TokenType::Dedent, "Expected dedent after else block");
+            self.consume(TokenType::Colon, "Expected ':' after else")?;
+            let else_block = self.block("else")?;
             Some(else_block)
         } else {
             None
         };
 
-        Some(Stmt::If {
+        Ok(StmtKind::If {
             condition,
             then_branch,
             else_branch,
         })
     }
 
-    fn parse_single_target(&mut self) -> Option<Target> {
+    fn parse_single_target(&mut self) -> ParseResult<Target> {
         if self.matches(&[TokenType::LParen]) {
             let mut elements = vec![];
 
@@ -281,36 +327,24 @@ impl<'prs> Parser<'prs> {
                 }
             }
 
-            self.consume(TokenType::RParen, "Expected ')' after tuple pattern");
-            return Some(Target::Tuple(elements));
+            self.consume(TokenType::RParen, "Expected ')' after tuple pattern")?;
+            return Ok(Target::Tuple(elements));
         }
 
-        if self.matches(&[TokenType::Identifier]) {
-            let name = if let Some(LiteralValue::Identifier(name)) = &self.peek_previous().literal {
-                name.clone()
-            } else {
-                eprintln!("Invalid identifier in target");
-                return None;
-            };
+        let name = self.identifier("Expected name or tuple in target")?;
 
-            if self.matches(&[TokenType::Dot]) {
-                let attr_token = self.consume(TokenType::Identifier, "Expected attribute name")?;
-                if let Some(LiteralValue::Identifier(attr_name)) = &attr_token.literal {
-                    return Some(Target::Attribute {
-                        object: Box::new(Expr::Variable(name)),
-                        name: attr_name.clone(),
-                    });
-                }
-            }
-
-            return Some(Target::Name(name));
+        if self.matches(&[TokenType::Dot]) {
+            let attr_name = self.identifier("Expected attribute name")?;
+            return Ok(Target::Attribute {
+                object: Box::new(Expr::Variable(name)),
+                name: attr_name,
+            });
         }
 
-        eprintln!("Expected name or tuple in for loop target");
-        None
+        Ok(Target::Name(name))
     }
 
-    fn parse_target_list(&mut self) -> Option<Target> {
+    fn parse_target_list(&mut self) -> ParseResult<Target> {
         let mut targets = vec![self.parse_single_target()?];
 
         while self.matches(&[TokenType::Comma]) {
@@ -318,58 +352,58 @@ impl<'prs> Parser<'prs> {
         }
 
         if targets.len() == 1 {
-            Some(targets.remove(0))
+            Ok(targets.remove(0))
         } else {
-            Some(Target::Tuple(targets))
+            Ok(Target::Tuple(targets))
         }
     }
 
-    fn for_statement(&mut self) -> Option<Stmt> {
+    fn for_statement(&mut self) -> ParseResult<StmtKind> {
         let target = self.parse_target_list()?;
 
-        self.consume(TokenType::In, "Expected 'in' after loop variable");
+        self.consume(TokenType::In, "Expected 'in' after loop variable")?;
 
         let iterable = self.expression()?;
 
-        self.consume(TokenType::Colon, "Expected ':' after iterable");
-        self.consume(TokenType::Newline, "Expected newline after ':'");
-        self.consume(TokenType::Indent, "Expected indent after for loop");
-
-        let mut body = vec![];
-        while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-            body.push(self.declaration()?);
-        }
+        self.consume(TokenType::Colon, "Expected ':' after iterable")?;
+        let body = self.block("for")?;
+        let else_branch = self.loop_else()?;
 
-        self.consume(TokenType::Dedent, "Expected dedent after for block");
-
-        Some(Stmt::For {
+        Ok(StmtKind::For {
             target,
             iterable,
             body,
+            else_branch,
         })
     }
 
-    fn while_statement(&mut self) -> Option<Stmt> {
+    fn while_statement(&mut self) -> ParseResult<StmtKind> {
         let condition = self.expression()?;
-        self.consume(TokenType::Colon, "Expected ':' after while condition");
-        self.consume(TokenType::Newline, "Expected newline after ':'");
-        self.consume(TokenType::Indent, "Expected indent after while");
+        self.consume(TokenType::Colon, "Expected ':' after while condition")?;
+        let body = self.block("while")?;
+        let else_branch = self.loop_else()?;
 
-        let mut body = vec![];
-        while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-            body.push(self.declaration()?);
-        }
+        Ok(StmtKind::While {
+            condition,
+            body,
+            else_branch,
+        })
+    }
 
-        self.consume(TokenType::Dedent, "Expected dedent after while block");
+    fn loop_else(&mut self) -> ParseResult<Option<Vec<Stmt>>> {
+        if !self.matches(&[TokenType::Else]) {
+            return Ok(None);
+        }
 
-        Some(Stmt::While { condition, body })
+        self.consume(TokenType::Colon, "Expected ':' after else")?;
+        Ok(Some(self.block("else")?))
     }
 
-    fn expression(&mut self) -> Option<Expr> {
+    fn expression(&mut self) -> ParseResult<Expr> {
         self.or()
     }
 
-    fn tuple_or_expression(&mut self) -> Option<Expr> {
+    fn tuple_or_expression(&mut self) -> ParseResult<Expr> {
         let mut exprs = vec![self.or()?];
 
         while self.matches(&[TokenType::Comma]) {
@@ -377,13 +411,13 @@ impl<'prs> Parser<'prs> {
         }
 
         if exprs.len() == 1 {
-            Some(exprs.remove(0))
+            Ok(exprs.remove(0))
         } else {
-            Some(Expr::Tuple(exprs))
+            Ok(Expr::Tuple(exprs))
         }
     }
 
-    fn or(&mut self) -> Option<Expr> {
+    fn or(&mut self) -> ParseResult<Expr> {
         let mut expr = self.and()?;
         while self.matches(&[TokenType::Or]) {
             let op = self.peek_previous().token_type;
@@ -394,24 +428,38 @@ impl<'prs> Parser<'prs> {
                 right: Box::new(right),
             };
         }
-        Some(expr)
+        Ok(expr)
     }
 
-    fn and(&mut self) -> Option<Expr> {
-        let mut expr = self.bitwise_or()?;
+    fn and(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.not()?;
         while self.matches(&[TokenType::And]) {
             let op = self.peek_previous().token_type;
-            let right = self.bitwise_or()?;
+            let right = self.not()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 op,
                 right: Box::new(right),
             };
         }
-        Some(expr)
+        Ok(expr)
     }
 
-    fn bitwise_or(&mut self) -> Option<Expr> {
+    // `not` binds more loosely than comparisons, so `not a in b` negates the
+    // membership test.
+    fn not(&mut self) -> ParseResult<Expr> {
+        if self.matches(&[TokenType::Not]) {
+            let expr = self.not()?;
+            return Ok(Expr::Unary {
+                op: TokenType::Not,
+                expr: Box::new(expr),
+            });
+        }
+
+        self.comparison()
+    }
+
+    fn bitwise_or(&mut self) -> ParseResult<Expr> {
         let mut expr = self.bitwise_xor()?;
 
         while self.matches(&[TokenType::Pipe]) {
@@ -424,10 +472,10 @@ impl<'prs> Parser<'prs> {
             };
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn bitwise_xor(&mut self) -> Option<Expr> {
+    fn bitwise_xor(&mut self) -> ParseResult<Expr> {
         let mut expr = self.bitwise_and()?;
 
         while self.matches(&[TokenType::Caret]) {
@@ -440,15 +488,15 @@ impl<'prs> Parser<'prs> {
             };
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn bitwise_and(&mut self) -> Option<Expr> {
-        let mut expr = self.equality()?;
+    fn bitwise_and(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.shift()?;
 
         while self.matches(&[TokenType::Ampersand]) {
             let op = self.peek_previous().token_type;
-            let right = self.equality()?;
+            let right = self.shift()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 op,
@@ -456,15 +504,15 @@ impl<'prs> Parser<'prs> {
             };
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn equality(&mut self) -> Option<Expr> {
-        let mut expr = self.comparison()?;
+    fn shift(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.term()?;
 
-        while self.matches(&[TokenType::EqualEqual, TokenType::NotEqual]) {
+        while self.matches(&[TokenType::LeftShift, TokenType::RightShift]) {
             let op = self.peek_previous().token_type;
-            let right = self.comparison()?;
+            let right = self.term()?;
             expr = Expr::Binary {
                 left: Box::new(expr),
                 op,
@@ -472,32 +520,59 @@ impl<'prs> Parser<'prs> {
             };
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn comparison(&mut self) -> Option<Expr> {
-        let mut expr = self.term()?;
+    // Comparisons chain, so `a < b < c` holds both `<` operators and the
+    // `b` and `c` operands.
+    fn comparison(&mut self) -> ParseResult<Expr> {
+        let left = self.bitwise_or()?;
+        let mut ops = vec![];
+        let mut comparators = vec![];
 
-        while self.matches(&[
-            TokenType::Less,
-            TokenType::LessEqual,
-            TokenType::Greater,
-            TokenType::GreaterEqual,
-            TokenType::Is,
-        ]) {
-            let op = self.peek_previous().token_type;
-            let right = self.term()?;
-            expr = Expr::Binary {
-                left: Box::new(expr),
-                op,
-                right: Box::new(right),
-            };
+        while let Some(op) = self.comparison_operator() {
+            ops.push(op);
+            comparators.push(self.bitwise_or()?);
+        }
+
+        if ops.is_empty() {
+            Ok(left)
+        } else {
+            Ok(Expr::Compare {
+                left: Box::new(left),
+                ops,
+                comparators,
+            })
         }
+    }
+
+    // `not in` and `is not` are single operators spelled with two tokens.
+    fn comparison_operator(&mut self) -> Option<CmpOp> {
+        let op = match self.peek().token_type {
+            TokenType::EqualEqual => CmpOp::Equal,
+            TokenType::NotEqual | TokenType::LessGreater => CmpOp::NotEqual,
+            TokenType::Less => CmpOp::Less,
+            TokenType::LessEqual => CmpOp::LessEqual,
+            TokenType::Greater => CmpOp::Greater,
+            TokenType::GreaterEqual => CmpOp::GreaterEqual,
+            TokenType::In => CmpOp::In,
+            TokenType::Is if self.check_next(&TokenType::Not) => {
+                self.advance();
+                CmpOp::IsNot
+            }
+            TokenType::Is => CmpOp::Is,
+            TokenType::Not if self.check_next(&TokenType::In) => {
+                self.advance();
+                CmpOp::NotIn
+            }
+            _ => return None,
+        };
 
-        Some(expr)
+        self.advance();
+        Some(op)
     }
 
-    fn term(&mut self) -> Option<Expr> {
+    fn term(&mut self) -> ParseResult<Expr> {
         let mut expr = self.factor()?;
 
         while self.matches(&[TokenType::Plus, TokenType::Minus]) {
@@ -510,10 +585,10 @@ impl<'prs> Parser<'prs> {
             };
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn power(&mut self) -> Option<Expr> {
+    fn power(&mut self) -> ParseResult<Expr> {
         let mut expr = self.unary()?;
 
         if self.matches(&[TokenType::StarStar]) {
@@ -526,10 +601,10 @@ impl<'prs> Parser<'prs> {
             };
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn factor(&mut self) -> Option<Expr> {
+    fn factor(&mut self) -> ParseResult<Expr> {
         let mut expr = self.power()?;
 
         while self.matches(&[TokenType::Star, TokenType::Slash, TokenType::Modulo]) {
@@ -542,14 +617,14 @@ impl<'prs> Parser<'prs> {
             };
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn unary(&mut self) -> Option<Expr> {
-        if self.matches(&[TokenType::Minus, TokenType::Not, TokenType::Tilde]) {
+    fn unary(&mut self) -> ParseResult<Expr> {
+        if self.matches(&[TokenType::Minus, TokenType::Tilde]) {
             let op = self.peek_previous().token_type;
             let expr = self.unary()?;
-            return Some(Expr::Unary {
+            return Ok(Expr::Unary {
                 op,
                 expr: Box::new(expr),
             });
@@ -558,36 +633,19 @@ impl<'prs> Parser<'prs> {
         self.call()
     }
 
-    fn parse_lambda_expr(&mut self) -> Option<Expr> {
-        let mut params = vec![];
-
-        if self.check(&TokenType::Identifier) {
-            loop {
-                let token = self.advance();
-                if let Some(LiteralValue::Identifier(name)) = &token.literal {
-                    params.push(name.clone());
-                } else {
-                    eprintln!("Expected identifier in lambda parameters");
-                    return None;
-                }
-
-                if !self.matches(&[TokenType::Comma]) {
-                    break;
-                }
-            }
-        }
-
+    fn parse_lambda_expr(&mut self) -> ParseResult<Expr> {
+        let params = self.parameters(TokenType::Colon)?;
         self.consume(TokenType::Colon, "Expected ':' after lambda parameters")?;
 
         let body = self.expression()?;
 
-        Some(Expr::Lambda {
+        Ok(Expr::Lambda {
             params,
             body: Box::new(body),
         })
     }
 
-    fn call(&mut self) -> Option<Expr> {
+    fn call(&mut self) -> ParseResult<Expr> {
         let mut expr = self.primary()?;
 
         loop {
@@ -603,67 +661,99 @@ impl<'prs> Parser<'prs> {
                     }
                 }
 
-                self.consume(TokenType::RParen, "Expected ')' after arguments");
+                self.consume(TokenType::RParen, "Expected ')' after arguments")?;
                 expr = Expr::Call {
                     callee: Box::new(expr),
                     args,
                 };
             } else if self.matches(&[TokenType::Dot]) {
-                let name_token =
-                    self.consume(TokenType::Identifier, "Expected attribute name after '.'")?;
-                if let Some(LiteralValue::Identifier(name)) = &name_token.literal {
-                    expr = Expr::Get {
-                        object: Box::new(expr),
-                        name: name.clone(),
-                    };
-                } else {
-                    eprintln!("Expected identifier after '.'");
-                    return None;
-                }
-            } else if self.matches(&[TokenType::LBracket]) {
-                let index = self.expression()?;
-                self.consume(TokenType::RBracket, "Expected ']' after index");
-                expr = Expr::Index {
+                let name = self.identifier("Expected attribute name after '.'")?;
+                expr = Expr::Get {
                     object: Box::new(expr),
-                    index: Box::new(index),
+                    name,
                 };
+            } else if self.matches(&[TokenType::LBracket]) {
+                expr = self.subscript(expr)?;
             } else {
                 break;
             }
         }
 
-        Some(expr)
+        Ok(expr)
     }
 
-    fn primary(&mut self) -> Option<Expr> {
-        if self.matches(&[TokenType::Int]) {
-            if let Some(LiteralValue::Int(i)) = &self.peek_previous().literal {
-                return Some(Expr::Literal(LiteralValue::Int(*i)));
+    // Parses `[index]` or `[lower:upper]`, where either slice bound may be
+    // omitted.
+    fn subscript(&mut self, object: Expr) -> ParseResult<Expr> {
+        let object = Box::new(object);
+
+        let lower = if self.matches(&[TokenType::Colon]) {
+            None
+        } else {
+            let index = Box::new(self.expression()?);
+            if !self.matches(&[TokenType::Colon]) {
+                self.consume(TokenType::RBracket, "Expected ']' after index")?;
+                return Ok(Expr::Index { object, index });
             }
+            Some(index)
+        };
+
+        let upper = if self.check(&TokenType::RBracket) {
+            None
+        } else {
+            Some(Box::new(self.expression()?))
+        };
+        self.consume(TokenType::RBracket, "Expected ']' after slice")?;
+
+        Ok(Expr::Slice {
+            object,
+            lower,
+            upper,
+        })
+    }
+
+    fn primary(&mut self) -> ParseResult<Expr> {
+        if self.matches(&[TokenType::Int])
+            && let Some(LiteralValue::Int(i)) = &self.peek_previous().literal
+        {
+            return Ok(Expr::Literal(LiteralValue::Int(*i)));
         }
 
-        if self.matches(&[TokenType::Float]) {
-            if let Some(LiteralValue::Float(f)) = &self.peek_previous().literal {
-                return Some(Expr::Literal(LiteralValue::Float(*f)));
-            }
+        if self.matches(&[TokenType::Long])
+            && let Some(LiteralValue::Long(n)) = &self.peek_previous().literal
+        {
+            return Ok(Expr::Literal(LiteralValue::Long(n.clone())));
         }
 
-        if self.matches(&[TokenType::String]) {
-            if let Some(LiteralValue::String(s)) = &self.peek_previous().literal {
-                return Some(Expr::Literal(LiteralValue::String(s.clone())));
-            }
+        if self.matches(&[TokenType::Float])
+            && let Some(LiteralValue::Float(f)) = &self.peek_previous().literal
+        {
+            return Ok(Expr::Literal(LiteralValue::Float(*f)));
         }
 
-        if self.matches(&[TokenType::Identifier]) {
-            if let Some(LiteralValue::Identifier(name)) = &self.peek_previous().literal {
-                return Some(Expr::Variable(name.clone()));
+        if self.matches(&[TokenType::String])
+            && let Some(LiteralValue::String(s)) = &self.peek_previous().literal
+        {
+            // Adjacent string literals are concatenated: "abc" "def" == "abcdef".
+            let mut value = s.clone();
+            while self.matches(&[TokenType::String]) {
+                if let Some(LiteralValue::String(next)) = &self.peek_previous().literal {
+                    value.extend_from_slice(next);
+                }
             }
+            return Ok(Expr::Literal(LiteralValue::String(value)));
+        }
+
+        if self.matches(&[TokenType::Identifier])
+            && let Some(LiteralValue::Identifier(name)) = &self.peek_previous().literal
+        {
+            return Ok(Expr::Variable(name.clone()));
         }
 
         if self.matches(&[TokenType::LParen]) {
             if self.check(&TokenType::RParen) {
                 self.advance();
-                return Some(Expr::Tuple(vec![]));
+                return Ok(Expr::Tuple(vec![]));
             }
 
             let mut exprs = vec![self.expression()?];
@@ -677,12 +767,12 @@ impl<'prs> Parser<'prs> {
                 exprs.push(self.expression()?);
             }
 
-            self.consume(TokenType::RParen, "Expected ')' after expression");
+            self.consume(TokenType::RParen, "Expected ')' after expression")?;
 
             return if has_comma || exprs.len() > 1 {
-                Some(Expr::Tuple(exprs))
+                Ok(Expr::Tuple(exprs))
             } else {
-                Some(Expr::Grouping(Box::new(exprs.into_iter().next().unwrap())))
+                Ok(Expr::Grouping(Box::new(exprs.into_iter().next().unwrap())))
             };
         }
 
@@ -698,8 +788,8 @@ impl<'prs> Parser<'prs> {
                 }
             }
 
-            self.consume(TokenType::RBracket, "Expected ']' after list literal");
-            return Some(Expr::List(elements));
+            self.consume(TokenType::RBracket, "Expected ']' after list literal")?;
+            return Ok(Expr::List(elements));
         }
 
         if self.matches(&[TokenType::LBrace]) {
@@ -711,7 +801,7 @@ impl<'prs> Parser<'prs> {
                     self.consume(
                         TokenType::Colon,
                         "Expected ':' between key and value in dict",
-                    );
+                    )?;
                     let value = self.expression()?;
                     pairs.push((key, value));
 
@@ -721,68 +811,74 @@ impl<'prs> Parser<'prs> {
                 }
             }
 
-            self.consume(TokenType::RBrace, "Expected '}' after dict literal");
-            return Some(Expr::Dict(pairs));
+            self.consume(TokenType::RBrace, "Expected '}' after dict literal")?;
+            return Ok(Expr::Dict(pairs));
         }
 
         if self.matches(&[TokenType::Lambda]) {
             return self.parse_lambda_expr();
         }
 
-        None
+        Err(self.error(&[], "Expected expression"))
     }
 
-    fn function_declaration(&mut self) -> Option<Stmt> {
-        let token = self.advance();
-        let name = if let Some(LiteralValue::Identifier(name)) = &token.literal {
-            name.clone()
-        } else {
-            eprintln!("Expected function name after 'def'");
-            return None;
-        };
+    fn function_declaration(&mut self) -> ParseResult<StmtKind> {
+        let name = self.identifier("Expected function name after 'def'")?;
 
-        self.consume(TokenType::LParen, "Expected '(' after function name");
+        self.consume(TokenType::LParen, "Expected '(' after function name")?;
 
-        let mut params = vec![];
-        if !self.check(&TokenType::RParen) {
-            loop {
-                let param_token = self.advance();
-                if let Some(LiteralValue::Identifier(name)) = &param_token.literal {
-                    params.push(name.to_string());
-                } else {
-                    eprintln!("Expected parameter name");
-                    return None;
-                }
+        let params = self.parameters(TokenType::RParen)?;
+        self.consume(TokenType::RParen, "Expected ')' after parameters")?;
+        self.consume(TokenType::Colon, "Expected ':' after function header")?;
+        let body = self.block("def")?;
 
-                if !self.matches(&[TokenType::Comma]) {
-                    break;
-                }
+        Ok(StmtKind::FunctionDef { name, params, body })
+    }
+
+    fn parameters(&mut self, terminator: TokenType) -> ParseResult<Parameters> {
+        let mut params = Parameters::default();
+
+        while !self.check(&terminator) {
+            if self.matches(&[TokenType::Star]) {
+                params.varargs = Some(self.identifier("Expected parameter name after '*'")?);
+                break;
+            }
+
+            params.positional.push(self.parameter()?);
+
+            if self.matches(&[TokenType::Equal]) {
+                params.defaults.push(self.expression()?);
+            } else if !params.defaults.is_empty() {
+                return Err(self.error(
+                    &[TokenType::Equal],
+                    "Non-default parameter follows default parameter",
+                ));
+            }
+
+            if !self.matches(&[TokenType::Comma]) {
+                break;
             }
         }
 
-        self.consume(TokenType::RParen, "Expected ')' after parameters");
-        self.consume(TokenType::Colon, "Expected ':' after function header");
-        self.consume(TokenType::Newline, "Expected newline after ':'");
-        self.consume(TokenType::Indent, "Expected indent before function body");
+        Ok(params)
+    }
 
-        let mut body = vec![];
-        while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-            body.push(self.declaration()?);
+    fn parameter(&mut self) -> ParseResult<Target> {
+        if !self.matches(&[TokenType::LParen]) {
+            return Ok(Target::Name(self.identifier("Expected parameter name")?));
         }
 
-        self.consume(TokenType::Dedent, "Expected dedent after function body");
+        let mut targets = vec![self.parameter()?];
+        while self.matches(&[TokenType::Comma]) && !self.check(&TokenType::RParen) {
+            targets.push(self.parameter()?);
+        }
 
-        Some(Stmt::FunctionDef { name, params, body })
+        self.consume(TokenType::RParen, "Expected ')' after tuple parameter")?;
+        Ok(Target::Tuple(targets))
     }
 
-    fn class_declaration(&mut self) -> Option<Stmt> {
-        let token = self.advance();
-        let name = if let Some(LiteralValue::Identifier(name)) = &token.literal {
-            name.clone()
-        } else {
-            eprintln!("Expected class name after 'class'");
-            return None;
-        };
+    fn class_declaration(&mut self) -> ParseResult<StmtKind> {
+        let name = self.identifier("Expected class name after 'class'")?;
 
         let base = if self.matches(&[TokenType::LParen]) {
             let expr = self.expression()?;
@@ -793,61 +889,158 @@ impl<'prs> Parser<'prs> {
         };
 
         self.consume(TokenType::Colon, "Expected ':' after class header")?;
-        self.consume(TokenType::Newline, "Expected newline after ':'")?;
-        self.consume(TokenType::Indent, "Expected indent after class header")?;
+        let body = self.block("class")?;
 
-        let mut body = vec![];
-        while !self.check(&TokenType::Dedent) && !self.is_at_end() {
-            body.push(self.declaration()?);
-        }
-
-        self.consume(TokenType::Dedent, "Expected dedent after class body")?;
-
-        Some(Stmt::ClassDef { name, base, body })
+        Ok(StmtKind::ClassDef { name, base, body })
     }
 
-    fn import_statement(&mut self) -> Option<Stmt> {
+    fn import_statement(&mut self) -> ParseResult<StmtKind> {
         let mut modules = vec![];
 
         loop {
-            let module_token = self.consume(TokenType::Identifier, "Expected module name")?;
-            if let Some(LiteralValue::Identifier(name)) = &module_token.literal {
-                modules.push(name.clone());
-            }
+            modules.push(self.identifier("Expected module name")?);
 
             if !self.matches(&[TokenType::Comma]) {
                 break;
             }
         }
 
-        self.consume(TokenType::Newline, "Expected newline after import");
-        Some(Stmt::Import(modules))
+        self.end_statement("Expected newline after import")?;
+        Ok(StmtKind::Import(modules))
     }
 
-    fn import_from_statement(&mut self) -> Option<Stmt> {
-        let module_token = self.consume(TokenType::Identifier, "Expected module name")?;
-        let module = if let Some(LiteralValue::Identifier(name)) = &module_token.literal {
-            name.clone()
-        } else {
-            return None;
-        };
+    fn import_from_statement(&mut self) -> ParseResult<StmtKind> {
+        let module = self.identifier("Expected module name")?;
 
         self.consume(TokenType::Import, "Expected 'import' after module name")?;
 
         let mut names = vec![];
         loop {
-            let name_token = self.consume(TokenType::Identifier, "Expected import name")?;
-            if let Some(LiteralValue::Identifier(name)) = &name_token.literal {
-                names.push(name.clone());
-            }
+            names.push(self.identifier("Expected import name")?);
 
             if !self.matches(&[TokenType::Comma]) {
                 break;
             }
         }
 
-        self.consume(TokenType::Newline, "Expected newline after from import");
-        Some(Stmt::FromImport { module, names })
+        self.end_statement("Expected newline after from import")?;
+        Ok(StmtKind::FromImport { module, names })
+    }
+
+    fn block(&mut self, keyword: &str) -> ParseResult<Vec<Stmt>> {
+        if !self.check(&TokenType::Newline) && !self.is_at_end() {
+            return self.inline_suite();
+        }
+
+        self.consume(TokenType::Newline, "Expected newline after ':'")?;
+        self.consume(
+            TokenType::Indent,
+            &format!("Expected indented block after '{keyword}'"),
+        )?;
+
+        let mut body = vec![];
+
+        while !self.check(&TokenType::Dedent) && !self.is_at_end() {
+            if self.matches(&[TokenType::Newline]) {
+                continue;
+            }
+
+            let start = self.current;
+            match self.declaration() {
+                Ok(stmt) => body.push(stmt),
+                Err(err) => self.recover(err, start),
+            }
+        }
+
+        self.end_block(&format!("Expected dedent after '{keyword}' block"))?;
+        Ok(body)
+    }
+
+    // A suite on the same line as its ':' holds only simple statements, which
+    // run until the end of the line.
+    fn inline_suite(&mut self) -> ParseResult<Vec<Stmt>> {
+        let mut body = vec![];
+
+        loop {
+            if is_compound_keyword(self.peek().token_type) {
+                return Err(self.error(&[], "Expected simple statement"));
+            }
+
+            body.push(self.declaration()?);
+
+            if self.peek_previous().token_type == TokenType::Newline || self.is_at_end() {
+                return Ok(body);
+            }
+        }
+    }
+
+    fn recover(&mut self, err: ParseError, start: usize) {
+        self.errors.push(err);
+        self.synchronize(is_compound_keyword(self.tokens[start].token_type));
+
+        if self.current == start {
+            self.advance();
+        }
+    }
+
+    // Skips the rest of a broken statement, including any block indented
+    // under it, stopping at the start of the next statement at the same depth.
+    // A broken compound statement also takes its elif/else/except/finally
+    // clauses with it, so they aren't reported as errors of their own.
+    fn synchronize(&mut self, compound: bool) {
+        let mut depth = 0;
+
+        while !self.is_at_end() {
+            match self.peek().token_type {
+                TokenType::Newline if depth == 0 => {
+                    self.advance();
+                    if !self.check(&TokenType::Indent) && !self.at_clause(compound) {
+                        return;
+                    }
+                    continue;
+                }
+                TokenType::Semicolon if depth == 0 && !compound => {
+                    self.advance();
+                    return;
+                }
+                TokenType::Indent => depth += 1,
+                TokenType::Dedent if depth == 0 => return,
+                TokenType::Dedent => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance();
+                        if !self.at_clause(compound) {
+                            return;
+                        }
+                        continue;
+                    }
+                }
+                token_type
+                    if depth == 0 && is_statement_keyword(token_type) && self.at_line_start() =>
+                {
+                    return;
+                }
+                _ => {}
+            }
+
+            self.advance();
+        }
+    }
+
+    fn at_clause(&self, compound: bool) -> bool {
+        compound
+            && matches!(
+                self.peek().token_type,
+                TokenType::Elif | TokenType::Else | TokenType::Except | TokenType::Finally
+            )
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.current == 0
+            || matches!(
+                self.peek_previous().token_type,
+                TokenType::Newline | TokenType::Indent | TokenType::Dedent | TokenType::Semicolon
+            )
     }
 
     fn advance(&mut self) -> &Token {
@@ -867,12 +1060,62 @@ impl<'prs> Parser<'prs> {
         false
     }
 
-    fn consume(&mut self, token_type: TokenType, msg: &str) -> Option<&Token> {
+    fn consume(&mut self, token_type: TokenType, msg: &str) -> ParseResult<&Token> {
         if self.check(&token_type) {
-            return Some(self.advance());
+            return Ok(self.advance());
+        }
+        Err(self.error(&[token_type], msg))
+    }
+
+    // Source text may end without a trailing newline or the dedents closing
+    // its last block, so running out of tokens also ends a statement/block.
+    fn end_statement(&mut self, msg: &str) -> ParseResult<()> {
+        if self.matches(&[TokenType::Semicolon]) {
+            if self.check(&TokenType::Newline) || self.is_at_end() {
+                return self.end_statement(msg);
+            }
+            if is_compound_keyword(self.peek().token_type) {
+                return Err(self.error(&[], "Expected simple statement after ';'"));
+            }
+            return Ok(());
+        }
+
+        if !self.is_at_end() {
+            self.consume(TokenType::Newline, msg)?;
+        }
+        Ok(())
+    }
+
+    fn at_statement_end(&self) -> bool {
+        self.check(&TokenType::Newline) || self.check(&TokenType::Semicolon) || self.is_at_end()
+    }
+
+    fn end_block(&mut self, msg: &str) -> ParseResult<()> {
+        if !self.is_at_end() {
+            self.consume(TokenType::Dedent, msg)?;
+        }
+        Ok(())
+    }
+
+    fn identifier(&mut self, msg: &str) -> ParseResult<String> {
+        let token = self.consume(TokenType::Identifier, msg)?;
+        match &token.literal {
+            Some(LiteralValue::Identifier(name)) => Ok(name.clone()),
+            _ => Err(self.error_at(self.peek_previous(), &[TokenType::Identifier], msg)),
+        }
+    }
+
+    fn error(&self, expected: &[TokenType], msg: &str) -> ParseError {
+        self.error_at(self.peek(), expected, msg)
+    }
+
+    fn error_at(&self, token: &Token, expected: &[TokenType], msg: &str) -> ParseError {
+        ParseError {
+            expected: expected.to_vec(),
+            found: token.clone(),
+            span: token.span,
+            message: msg.to_string(),
         }
-        eprintln!("{msg}");
-        None
     }
 
     fn check(&self, token_type: &TokenType) -> bool {
@@ -882,6 +1125,12 @@ impl<'prs> Parser<'prs> {
         &self.peek().token_type == token_type
     }
 
+    fn check_next(&self, token_type: &TokenType) -> bool {
+        self.tokens
+            .get(self.current + 1)
+            .is_some_and(|token| &token.token_type == token_type)
+    }
+
     fn is_at_end(&self) -> bool {
         self.peek().token_type == TokenType::Eof
     }
@@ -894,3 +1143,37 @@ impl<'prs> Parser<'prs> {
         &self.tokens[self.current - 1]
     }
 }
+
+fn is_statement_keyword(token_type: TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Def
+            | TokenType::Class
+            | TokenType::If
+            | TokenType::While
+            | TokenType::For
+            | TokenType::Try
+            | TokenType::Return
+            | TokenType::Print
+            | TokenType::Pass
+            | TokenType::Break
+            | TokenType::Continue
+            | TokenType::Import
+            | TokenType::From
+            | TokenType::Global
+            | TokenType::Del
+            | TokenType::Raise
+    )
+}
+
+fn is_compound_keyword(token_type: TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Def
+            | TokenType::Class
+            | TokenType::If
+            | TokenType::While
+            | TokenType::For
+            | TokenType::Try
+    )
+}
@@ -1,9 +1,10 @@
 use crate::core::{
-    ast::{Expr, Stmt, Target},
+    ast::{CmpOp, Expr, Stmt, StmtKind, Target},
+    error::ParseError,
     token::{LiteralValue, Token, TokenType},
 };
 
-use super::parser::Parser;
+use super::{lexer::Lexer, parser::Parser};
 
 fn create_token(token_type: TokenType, literal: Option<LiteralValue>) -> Token {
     Token::new(token_type, literal, (0, 0))
@@ -11,8 +12,20 @@ fn create_token(token_type: TokenType, literal: Option<LiteralValue>) -> Token {
 
 fn parse_tokens(tokens: Vec<Token>) -> Vec<Stmt> {
     let mut parser = Parser::new(&tokens);
-    parser.parse();
-    parser.statements
+    parser.parse().unwrap()
+}
+
+fn parse_source(src: &str) -> Vec<Stmt> {
+    let src = src.to_string();
+    let tokens = Lexer::new(&src).analyze().unwrap();
+    parse_tokens(tokens)
+}
+
+fn parse_errors(src: &str) -> Vec<ParseError> {
+    let src = src.to_string();
+    let tokens = Lexer::new(&src).analyze().unwrap();
+    let mut parser = Parser::new(&tokens);
+    parser.parse().unwrap_err()
 }
 
 #[test]
@@ -31,8 +44,8 @@ fn test_simple_assignment() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target, value } => {
+    match &statements[0].kind {
+        StmtKind::Assign { target, value } => {
             match target {
                 Target::Name(name) => assert_eq!(name, "x"),
                 _ => panic!("Expected name target"),
@@ -69,8 +82,8 @@ fn test_multiple_assignment() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target, value } => {
+    match &statements[0].kind {
+        StmtKind::Assign { target, value } => {
             match target {
                 Target::Tuple(targets) => {
                     assert_eq!(targets.len(), 2);
@@ -125,15 +138,16 @@ fn test_function_definition() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::FunctionDef { name, params, body } => {
+    match &statements[0].kind {
+        StmtKind::FunctionDef { name, params, body } => {
             assert_eq!(name, "test");
-            assert_eq!(params.len(), 2);
-            assert_eq!(params[0], "x");
-            assert_eq!(params[1], "y");
+            assert_eq!(params.positional.len(), 2);
+            assert!(matches!(&params.positional[0], Target::Name(name) if name == "x"));
+            assert!(matches!(&params.positional[1], Target::Name(name) if name == "y"));
+            assert!(params.defaults.is_empty() && params.varargs.is_none());
             assert_eq!(body.len(), 1);
-            match &body[0] {
-                Stmt::Return(Some(Expr::Variable(var))) => assert_eq!(var, "x"),
+            match &body[0].kind {
+                StmtKind::Return(Some(Expr::Variable(var))) => assert_eq!(var, "x"),
                 _ => panic!("Expected return statement"),
             }
         }
@@ -157,7 +171,7 @@ fn test_if_statement() {
         create_token(TokenType::Print, None),
         create_token(
             TokenType::String,
-            Some(LiteralValue::String("positive".to_string())),
+            Some(LiteralValue::String("positive".into())),
         ),
         create_token(TokenType::Newline, None),
         create_token(TokenType::Dedent, None),
@@ -168,7 +182,7 @@ fn test_if_statement() {
         create_token(TokenType::Print, None),
         create_token(
             TokenType::String,
-            Some(LiteralValue::String("not positive".to_string())),
+            Some(LiteralValue::String("not positive".into())),
         ),
         create_token(TokenType::Newline, None),
         create_token(TokenType::Dedent, None),
@@ -178,21 +192,21 @@ fn test_if_statement() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::If {
+    match &statements[0].kind {
+        StmtKind::If {
             condition,
             then_branch,
             else_branch,
         } => {
             match condition {
-                Expr::Binary { left, op, .. } => {
-                    assert_eq!(*op, TokenType::Greater);
+                Expr::Compare { left, ops, .. } => {
+                    assert_eq!(*ops, [CmpOp::Greater]);
                     match left.as_ref() {
                         Expr::Variable(name) => assert_eq!(name, "x"),
                         _ => panic!("Expected variable"),
                     }
                 }
-                _ => panic!("Expected binary expression"),
+                _ => panic!("Expected comparison"),
             }
             assert_eq!(then_branch.len(), 1);
             assert!(else_branch.is_some());
@@ -231,11 +245,12 @@ fn test_for_loop() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::For {
+    match &statements[0].kind {
+        StmtKind::For {
             target,
             iterable,
             body,
+            else_branch: None,
         } => {
             match target {
                 Target::Name(name) => assert_eq!(name, "i"),
@@ -283,11 +298,15 @@ fn test_while_loop() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::While { condition, body } => {
+    match &statements[0].kind {
+        StmtKind::While {
+            condition,
+            body,
+            else_branch: None,
+        } => {
             match condition {
-                Expr::Binary { .. } => {}
-                _ => panic!("Expected binary expression"),
+                Expr::Compare { .. } => {}
+                _ => panic!("Expected comparison"),
             }
             assert_eq!(body.len(), 1);
         }
@@ -305,7 +324,7 @@ fn test_try_except() {
         create_token(TokenType::Print, None),
         create_token(
             TokenType::String,
-            Some(LiteralValue::String("trying".to_string())),
+            Some(LiteralValue::String("trying".into())),
         ),
         create_token(TokenType::Newline, None),
         create_token(TokenType::Dedent, None),
@@ -316,7 +335,7 @@ fn test_try_except() {
         create_token(TokenType::Print, None),
         create_token(
             TokenType::String,
-            Some(LiteralValue::String("error".to_string())),
+            Some(LiteralValue::String("error".into())),
         ),
         create_token(TokenType::Newline, None),
         create_token(TokenType::Dedent, None),
@@ -326,16 +345,18 @@ fn test_try_except() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Try {
+    match &statements[0].kind {
+        StmtKind::Try {
             body,
             except_clauses,
+            finally_body,
         } => {
             assert_eq!(body.len(), 1);
             assert_eq!(except_clauses.len(), 1);
-            let (exception_type, except_body) = &except_clauses[0];
-            assert!(exception_type.is_none());
-            assert_eq!(except_body.len(), 1);
+            assert!(except_clauses[0].matcher.is_none());
+            assert!(except_clauses[0].target.is_none());
+            assert_eq!(except_clauses[0].body.len(), 1);
+            assert!(finally_body.is_none());
         }
         _ => panic!("Expected try statement"),
     }
@@ -361,13 +382,13 @@ fn test_class_definition() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::ClassDef { name, base, body } => {
+    match &statements[0].kind {
+        StmtKind::ClassDef { name, base, body } => {
             assert_eq!(name, "MyClass");
             assert!(base.is_none());
             assert_eq!(body.len(), 1);
-            match &body[0] {
-                Stmt::Pass => {}
+            match &body[0].kind {
+                StmtKind::Pass => {}
                 _ => panic!("Expected pass statement"),
             }
         }
@@ -395,8 +416,8 @@ fn test_binary_expressions() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target: _, value } => {
+    match &statements[0].kind {
+        StmtKind::Assign { target: _, value } => {
             match value {
                 Expr::Binary { left, op, .. } => {
                     assert_eq!(*op, TokenType::Plus);
@@ -431,8 +452,8 @@ fn test_bitwise_operators() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target: _, value } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { target: _, value } => match value {
             Expr::Binary { op, .. } => assert_eq!(*op, TokenType::Ampersand),
             _ => panic!("Expected binary expression"),
         },
@@ -449,10 +470,7 @@ fn test_list_and_dict_literals() {
         ),
         create_token(TokenType::Equal, None),
         create_token(TokenType::LBrace, None),
-        create_token(
-            TokenType::String,
-            Some(LiteralValue::String("key".to_string())),
-        ),
+        create_token(TokenType::String, Some(LiteralValue::String("key".into()))),
         create_token(TokenType::Colon, None),
         create_token(TokenType::LBracket, None),
         create_token(TokenType::Int, Some(LiteralValue::Int(1))),
@@ -467,13 +485,13 @@ fn test_list_and_dict_literals() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target: _, value } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { target: _, value } => match value {
             Expr::Dict(pairs) => {
                 assert_eq!(pairs.len(), 1);
                 let (key, val) = &pairs[0];
                 match key {
-                    Expr::Literal(LiteralValue::String(s)) => assert_eq!(s, "key"),
+                    Expr::Literal(LiteralValue::String(s)) => assert_eq!(s, b"key"),
                     _ => panic!("Expected string key"),
                 }
                 match val {
@@ -514,11 +532,11 @@ fn test_lambda_expression() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target: _, value } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { target: _, value } => match value {
             Expr::Lambda { params, body } => {
-                assert_eq!(params.len(), 1);
-                assert_eq!(params[0], "x");
+                assert_eq!(params.positional.len(), 1);
+                assert!(matches!(&params.positional[0], Target::Name(name) if name == "x"));
                 match body.as_ref() {
                     Expr::Binary { .. } => {}
                     _ => panic!("Expected binary expression in lambda body"),
@@ -556,16 +574,16 @@ fn test_import_statements() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 2);
 
-    match &statements[0] {
-        Stmt::Import(modules) => {
+    match &statements[0].kind {
+        StmtKind::Import(modules) => {
             assert_eq!(modules.len(), 1);
             assert_eq!(modules[0], "sys");
         }
         _ => panic!("Expected import statement"),
     }
 
-    match &statements[1] {
-        Stmt::FromImport { module, names } => {
+    match &statements[1].kind {
+        StmtKind::FromImport { module, names } => {
             assert_eq!(module, "os");
             assert_eq!(names.len(), 1);
             assert_eq!(names[0], "path");
@@ -604,8 +622,8 @@ fn test_indexing_and_attribute_access() {
     println!("{statements:#?}");
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target: _, value } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { target: _, value } => match value {
             Expr::Index { object, index } => {
                 match object.as_ref() {
                     Expr::Call { callee, .. } => match callee.as_ref() {
@@ -641,8 +659,8 @@ fn test_function_call_without_args() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Expression(expr) => match expr {
+    match &statements[0].kind {
+        StmtKind::Expression(expr) => match expr {
             Expr::Call { callee, args } => {
                 match callee.as_ref() {
                     Expr::Variable(name) => assert_eq!(name, "bruh"),
@@ -682,8 +700,8 @@ fn test_method_call_without_args() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target, value } => {
+    match &statements[0].kind {
+        StmtKind::Assign { target, value } => {
             match target {
                 Target::Name(name) => assert_eq!(name, "da"),
                 _ => panic!("Expected name target"),
@@ -718,10 +736,7 @@ fn test_list_literal_proper_parsing() {
         ),
         create_token(TokenType::Equal, None),
         create_token(TokenType::LBracket, None),
-        create_token(
-            TokenType::String,
-            Some(LiteralValue::String("da".to_string())),
-        ),
+        create_token(TokenType::String, Some(LiteralValue::String("da".into()))),
         create_token(TokenType::Comma, None),
         create_token(TokenType::Int, Some(LiteralValue::Int(1))),
         create_token(TokenType::RBracket, None),
@@ -732,8 +747,8 @@ fn test_list_literal_proper_parsing() {
     let statements = parse_tokens(tokens);
     assert_eq!(statements.len(), 1);
 
-    match &statements[0] {
-        Stmt::Assign { target, value } => {
+    match &statements[0].kind {
+        StmtKind::Assign { target, value } => {
             match target {
                 Target::Name(name) => assert_eq!(name, "bruh"),
                 _ => panic!("Expected name target"),
@@ -742,7 +757,7 @@ fn test_list_literal_proper_parsing() {
                 Expr::List(elements) => {
                     assert_eq!(elements.len(), 2);
                     match &elements[0] {
-                        Expr::Literal(LiteralValue::String(s)) => assert_eq!(s, "da"),
+                        Expr::Literal(LiteralValue::String(s)) => assert_eq!(s, b"da"),
                         _ => panic!("Expected string literal"),
                     }
                     match &elements[1] {
@@ -774,8 +789,8 @@ fn test_tuple_vs_grouping_in_parentheses() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Assign { value, .. } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { value, .. } => match value {
             Expr::Grouping(inner) => match inner.as_ref() {
                 Expr::Literal(LiteralValue::Int(42)) => {}
                 _ => panic!("Expected int literal in grouping"),
@@ -805,8 +820,8 @@ fn test_tuple_with_comma_in_parentheses() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Assign { value, .. } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { value, .. } => match value {
             Expr::Tuple(elements) => {
                 assert_eq!(elements.len(), 2);
                 match &elements[0] {
@@ -839,8 +854,8 @@ fn test_empty_tuple_in_parentheses() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Assign { value, .. } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { value, .. } => match value {
             Expr::Tuple(elements) => assert_eq!(elements.len(), 0),
             _ => panic!("Expected empty tuple, got: {value:#?}"),
         },
@@ -858,7 +873,7 @@ fn test_function_call_with_args() {
         create_token(TokenType::LParen, None),
         create_token(
             TokenType::String,
-            Some(LiteralValue::String("hello".to_string())),
+            Some(LiteralValue::String("hello".into())),
         ),
         create_token(TokenType::Comma, None),
         create_token(TokenType::Int, Some(LiteralValue::Int(42))),
@@ -868,8 +883,8 @@ fn test_function_call_with_args() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Expression(expr) => match expr {
+    match &statements[0].kind {
+        StmtKind::Expression(expr) => match expr {
             Expr::Call { callee, args } => {
                 match callee.as_ref() {
                     Expr::Variable(name) => assert_eq!(name, "print"),
@@ -877,7 +892,7 @@ fn test_function_call_with_args() {
                 }
                 assert_eq!(args.len(), 2);
                 match &args[0] {
-                    Expr::Literal(LiteralValue::String(s)) => assert_eq!(s, "hello"),
+                    Expr::Literal(LiteralValue::String(s)) => assert_eq!(s, b"hello"),
                     _ => panic!("Expected string argument"),
                 }
                 match &args[1] {
@@ -922,8 +937,8 @@ fn test_chained_method_calls() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Assign { value, .. } => match value {
+    match &statements[0].kind {
+        StmtKind::Assign { value, .. } => match value {
             Expr::Call { callee, .. } => match callee.as_ref() {
                 Expr::Get { object, name } => {
                     assert_eq!(name, "method2");
@@ -973,8 +988,8 @@ fn test_expression_vs_assignment_distinction() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Expression(expr) => match expr {
+    match &statements[0].kind {
+        StmtKind::Expression(expr) => match expr {
             Expr::Binary { left, op, right } => {
                 assert_eq!(*op, TokenType::Plus);
                 match left.as_ref() {
@@ -1010,8 +1025,8 @@ fn test_comma_separated_expressions_as_tuple() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Expression(expr) => match expr {
+    match &statements[0].kind {
+        StmtKind::Expression(expr) => match expr {
             Expr::Tuple(elements) => {
                 assert_eq!(elements.len(), 2);
                 match &elements[0] {
@@ -1045,8 +1060,8 @@ fn test_single_element_tuple_with_trailing_comma() {
     ];
 
     let statements = parse_tokens(tokens);
-    match &statements[0] {
-        Stmt::Expression(expr) => match expr {
+    match &statements[0].kind {
+        StmtKind::Expression(expr) => match expr {
             Expr::Tuple(elements) => {
                 assert_eq!(elements.len(), 1);
                 match &elements[0] {
@@ -1059,3 +1074,469 @@ fn test_single_element_tuple_with_trailing_comma() {
         _ => panic!("Expected expression statement"),
     }
 }
+
+#[test]
+fn test_missing_colon_error() {
+    let errors = parse_errors("if x\n    pass\n");
+
+    let err = &errors[0];
+    assert_eq!(err.expected, vec![TokenType::Colon]);
+    assert_eq!(err.found.token_type, TokenType::Newline);
+    assert_eq!(err.span, (4, 4));
+    assert_eq!(err.message, "Expected ':' after if condition");
+}
+
+#[test]
+fn test_missing_expression_error() {
+    let errors = parse_errors("x = \n");
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].expected.is_empty());
+    assert_eq!(errors[0].found.token_type, TokenType::Newline);
+    assert_eq!(errors[0].message, "Expected expression");
+}
+
+#[test]
+fn test_invalid_assignment_target_error() {
+    let errors = parse_errors("f() = 1\n");
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].found.token_type, TokenType::Equal);
+    assert_eq!(errors[0].span, (4, 5));
+    assert_eq!(errors[0].message, "Invalid assignment target");
+}
+
+#[test]
+fn test_errors_on_separate_lines_are_all_reported() {
+    let errors = parse_errors("a = (1 2)\nb = 2\nimport\nc = 3\n");
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].expected, vec![TokenType::RParen]);
+    assert_eq!(errors[1].expected, vec![TokenType::Identifier]);
+    assert_eq!(errors[1].message, "Expected module name");
+}
+
+#[test]
+fn test_recovery_reports_each_error_once() {
+    let src = "def f(x):\n    y = x +\n    return y\ndef g(1):\n    pass\n    pass\nclass C:\n    def m(self):\n        a = = 1\n        b = 2\n    z = 3 3\nprint f(1)\n";
+    let errors = parse_errors(src);
+
+    let messages: Vec<_> = errors.iter().map(|err| err.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Expected expression",
+            "Expected parameter name",
+            "Expected expression",
+            "Expected newline after assignment",
+        ]
+    );
+}
+
+#[test]
+fn test_recovery_skips_block_of_broken_header() {
+    let errors = parse_errors("while x\n    if y:\n        pass\n    z = 1\nw = )\n");
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message, "Expected ':' after while condition");
+    assert_eq!(errors[1].found.token_type, TokenType::RParen);
+}
+
+#[test]
+fn test_recovery_resumes_after_semicolon() {
+    let errors = parse_errors("x = 1 +; print 2 +\ny =\n");
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].found.token_type, TokenType::Semicolon);
+    assert_eq!(errors[1].found.token_type, TokenType::Newline);
+    assert_eq!(errors[2].found.token_type, TokenType::Newline);
+}
+
+#[test]
+fn test_recovery_ignores_keywords_inside_a_line() {
+    let errors = parse_errors(
+        "foo(print 1)
+x = 1 + print 2
+",
+    );
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].found.token_type, TokenType::Print);
+    assert_eq!(errors[1].found.token_type, TokenType::Print);
+}
+
+#[test]
+fn test_recovery_skips_clauses_of_broken_compound_statement() {
+    let errors = parse_errors(
+        "if x\n    y = 1\nelif z:\n    y = 2\nelse:\n    y = 3\ntry\n    f()\nexcept E:\n    pass\nfinally:\n    pass\nw = )\n",
+    );
+
+    let messages: Vec<_> = errors.iter().map(|err| err.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Expected ':' after if condition",
+            "Expected ':' after try",
+            "Expected expression",
+        ]
+    );
+}
+
+#[test]
+fn test_recovery_resumes_at_keyword_starting_a_line() {
+    let errors = parse_errors("if x:\nprint 1\nprint 2 +\n");
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message, "Expected indented block after 'if'");
+    assert_eq!(errors[1].found.token_type, TokenType::Newline);
+}
+
+#[test]
+fn test_adjacent_string_concatenation() {
+    let statements = parse_source("x = 'abc' \"def\" '''ghi'''\n");
+
+    match &statements[0].kind {
+        StmtKind::Assign { value, .. } => match value {
+            Expr::Literal(LiteralValue::String(s)) => assert_eq!(s, b"abcdefghi"),
+            _ => panic!("Expected string literal, got: {value:#?}"),
+        },
+        _ => panic!("Expected assignment statement"),
+    }
+}
+
+#[test]
+fn test_docstrings_in_definitions() {
+    let src = "class C:\n    \"\"\"A class.\n\n    With details.\n    \"\"\"\n    def f(self):\n        '''Method doc.'''\n        return 1\n";
+    let statements = parse_source(src);
+
+    match &statements[0].kind {
+        StmtKind::ClassDef { body, .. } => {
+            assert_eq!(body.len(), 2);
+            match &body[0].kind {
+                StmtKind::Expression(Expr::Literal(LiteralValue::String(doc))) => {
+                    assert_eq!(doc, b"A class.\n\n    With details.\n    ")
+                }
+                other => panic!("Expected docstring, got: {other:#?}"),
+            }
+            match &body[1].kind {
+                StmtKind::FunctionDef { body, .. } => assert_eq!(body.len(), 2),
+                other => panic!("Expected method, got: {other:#?}"),
+            }
+        }
+        _ => panic!("Expected class definition"),
+    }
+}
+
+#[test]
+fn test_membership_and_identity_operators() {
+    let statements = parse_source("a in b\na not in b\na is b\na is not b\nnot a in b\n");
+
+    let ops: Vec<_> = statements
+        .iter()
+        .map(|stmt| match &stmt.kind {
+            StmtKind::Expression(Expr::Compare { ops, .. }) => Some(ops[0]),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        ops,
+        [
+            Some(CmpOp::In),
+            Some(CmpOp::NotIn),
+            Some(CmpOp::Is),
+            Some(CmpOp::IsNot),
+            None,
+        ]
+    );
+
+    match &statements[4].kind {
+        StmtKind::Expression(Expr::Unary { op, expr }) => {
+            assert_eq!(*op, TokenType::Not);
+            assert!(matches!(expr.as_ref(), Expr::Compare { ops, .. } if ops[..] == [CmpOp::In]));
+        }
+        other => panic!("Expected negated membership test, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_chained_comparison() {
+    let statements = parse_source("a < b == c <> d\n");
+
+    match &statements[0].kind {
+        StmtKind::Expression(Expr::Compare {
+            left,
+            ops,
+            comparators,
+        }) => {
+            assert!(matches!(left.as_ref(), Expr::Variable(name) if name == "a"));
+            assert_eq!(*ops, [CmpOp::Less, CmpOp::Equal, CmpOp::NotEqual]);
+            assert_eq!(comparators.len(), 3);
+        }
+        other => panic!("Expected comparison, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_comparisons_bind_looser_than_bitwise_operators() {
+    let statements = parse_source("a & b == c | d\n");
+
+    match &statements[0].kind {
+        StmtKind::Expression(Expr::Compare {
+            left, comparators, ..
+        }) => {
+            assert!(matches!(
+                left.as_ref(),
+                Expr::Binary {
+                    op: TokenType::Ampersand,
+                    ..
+                }
+            ));
+            assert!(matches!(
+                comparators[0],
+                Expr::Binary {
+                    op: TokenType::Pipe,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Expected comparison, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_shift_precedence() {
+    let statements = parse_source("a & b << c + d\n");
+
+    match &statements[0].kind {
+        StmtKind::Expression(Expr::Binary {
+            op: TokenType::Ampersand,
+            right,
+            ..
+        }) => match right.as_ref() {
+            Expr::Binary {
+                op: TokenType::LeftShift,
+                right,
+                ..
+            } => assert!(matches!(
+                right.as_ref(),
+                Expr::Binary {
+                    op: TokenType::Plus,
+                    ..
+                }
+            )),
+            other => panic!("Expected shift, got: {other:#?}"),
+        },
+        other => panic!("Expected bitwise and, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_slice_forms() {
+    let statements = parse_source("a[1:2]\na[:2]\na[1:]\na[:]\n");
+
+    let bounds: Vec<_> = statements
+        .iter()
+        .map(|stmt| match &stmt.kind {
+            StmtKind::Expression(Expr::Slice { lower, upper, .. }) => {
+                (lower.is_some(), upper.is_some())
+            }
+            other => panic!("Expected slice, got: {other:#?}"),
+        })
+        .collect();
+    assert_eq!(
+        bounds,
+        [(true, true), (false, true), (true, false), (false, false)]
+    );
+}
+
+#[test]
+fn test_subscript_targets() {
+    let statements = parse_source("a[0] = 1\na[1:] = b\ndel a[0], a[:2]\n");
+
+    assert!(matches!(
+        &statements[0].kind,
+        StmtKind::Assign {
+            target: Target::Index { .. },
+            ..
+        }
+    ));
+    assert!(matches!(
+        &statements[1].kind,
+        StmtKind::Assign {
+            target: Target::Slice { .. },
+            ..
+        }
+    ));
+    match &statements[2].kind {
+        StmtKind::Del(Target::Tuple(targets)) => {
+            assert!(matches!(targets[0], Target::Index { .. }));
+            assert!(matches!(targets[1], Target::Slice { .. }));
+        }
+        other => panic!("Expected del of two targets, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_except_clause_forms() {
+    let statements =
+        parse_source("try:\n    f()\nexcept (A, B):\n    pass\nexcept C, (x, y):\n    pass\n");
+
+    match &statements[0].kind {
+        StmtKind::Try { except_clauses, .. } => {
+            assert!(matches!(except_clauses[0].matcher, Some(Expr::Tuple(_))));
+            assert!(except_clauses[0].target.is_none());
+            assert!(matches!(except_clauses[1].matcher, Some(Expr::Variable(_))));
+            assert!(matches!(except_clauses[1].target, Some(Target::Tuple(_))));
+        }
+        other => panic!("Expected try statement, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_try_finally() {
+    let statements = parse_source("try:\n    f()\nfinally:\n    g()\n    h()\n");
+
+    match &statements[0].kind {
+        StmtKind::Try {
+            except_clauses,
+            finally_body: Some(finally_body),
+            ..
+        } => {
+            assert!(except_clauses.is_empty());
+            assert_eq!(finally_body.len(), 2);
+        }
+        other => panic!("Expected try/finally statement, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_try_requires_handler() {
+    let errors = parse_errors("try:\n    f()\nx = 1\n");
+    assert_eq!(
+        errors[0].message,
+        "Expected 'except' or 'finally' after try block"
+    );
+}
+
+#[test]
+fn test_loop_else_clauses() {
+    let statements = parse_source(
+        "while a:\n    f()\nelse:\n    g()\nfor x in s:\n    f()\nelse:\n    g()\n    h()\n",
+    );
+
+    assert!(matches!(
+        &statements[0].kind,
+        StmtKind::While { else_branch: Some(else_branch), .. } if else_branch.len() == 1
+    ));
+    assert!(matches!(
+        &statements[1].kind,
+        StmtKind::For { else_branch: Some(else_branch), .. } if else_branch.len() == 2
+    ));
+}
+
+#[test]
+fn test_semicolon_separated_statements() {
+    let statements = parse_source("a = 1; b = 2;\nreturn; pass\n");
+
+    assert_eq!(statements.len(), 4);
+    assert!(matches!(statements[2].kind, StmtKind::Return(None)));
+    assert!(matches!(statements[3].kind, StmtKind::Pass));
+}
+
+#[test]
+fn test_inline_suites() {
+    let statements =
+        parse_source("def f(x): return x\nif a: f(1); f(2)\nelse: pass\nwhile b: break\nx = 1\n");
+
+    assert_eq!(statements.len(), 4);
+    assert!(matches!(&statements[0].kind, StmtKind::FunctionDef { body, .. } if body.len() == 1));
+    match &statements[1].kind {
+        StmtKind::If {
+            then_branch,
+            else_branch: Some(else_branch),
+            ..
+        } => {
+            assert_eq!(then_branch.len(), 2);
+            assert_eq!(else_branch.len(), 1);
+        }
+        other => panic!("Expected if statement, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_inline_suites_hold_only_simple_statements() {
+    let errors = parse_errors("if a: if b: pass\nx = 1; while b: pass\n");
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].message, "Expected simple statement");
+    assert_eq!(errors[1].message, "Expected simple statement after ';'");
+}
+
+#[test]
+fn test_print_items() {
+    let statements = parse_source("print\nprint a\nprint a, b,\nprint (a, b)\n");
+
+    let shapes: Vec<_> = statements
+        .iter()
+        .map(|stmt| match &stmt.kind {
+            StmtKind::Print {
+                items,
+                trailing_comma,
+            } => (items.len(), *trailing_comma),
+            other => panic!("Expected print, got: {other:#?}"),
+        })
+        .collect();
+    assert_eq!(shapes, [(0, false), (1, false), (2, true), (1, false)]);
+}
+
+#[test]
+fn test_parameter_forms() {
+    let statements =
+        parse_source("def f((a, (b, c)), d, e=1, *rest): pass\ng = lambda x=2, *y: x\n");
+
+    match &statements[0].kind {
+        StmtKind::FunctionDef { params, .. } => {
+            assert_eq!(params.positional.len(), 3);
+            match &params.positional[0] {
+                Target::Tuple(targets) => {
+                    assert!(matches!(&targets[0], Target::Name(name) if name == "a"));
+                    assert!(matches!(&targets[1], Target::Tuple(inner) if inner.len() == 2));
+                }
+                other => panic!("Expected tuple parameter, got: {other:#?}"),
+            }
+            assert_eq!(params.defaults.len(), 1);
+            assert_eq!(params.varargs.as_deref(), Some("rest"));
+        }
+        other => panic!("Expected function definition, got: {other:#?}"),
+    }
+
+    match &statements[1].kind {
+        StmtKind::Assign {
+            value: Expr::Lambda { params, .. },
+            ..
+        } => {
+            assert_eq!(params.positional.len(), 1);
+            assert_eq!(params.defaults.len(), 1);
+            assert_eq!(params.varargs.as_deref(), Some("y"));
+        }
+        other => panic!("Expected lambda assignment, got: {other:#?}"),
+    }
+}
+
+#[test]
+fn test_non_default_parameter_after_default() {
+    let errors = parse_errors("def f(a=1, b): pass\n");
+    assert_eq!(
+        errors[0].message,
+        "Non-default parameter follows default parameter"
+    );
+}
+
+#[test]
+fn test_empty_subscript_error() {
+    let errors = parse_errors("a[]\n");
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].found.token_type, TokenType::RBracket);
+    assert_eq!(errors[0].message, "Expected expression");
+}
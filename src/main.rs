@@ -1,31 +1,60 @@
 mod cli;
 mod core;
+mod diagnostics;
 mod frontend;
+mod runtime;
 
-use std::{env::args, process::exit};
+use std::{env::args, process::exit, thread};
 
 use cli::{repl::Repl, runner::run_file};
+use frontend::lexer::TabPolicy;
+
+// Deeply recursive Python programs recurse through the evaluator, so the
+// interpreter runs on a thread with a much larger stack than the default.
+const STACK_SIZE: usize = 512 * 1024 * 1024;
 
 fn main() {
     let args = args().skip(1).collect::<Vec<String>>();
 
+    let handle = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || run(args))
+        .expect("Error while spawning interpreter thread...");
+
+    if handle.join().is_err() {
+        exit(70);
+    }
+}
+
+fn run(mut args: Vec<String>) {
+    // `-tt` rejects indentation that mixes tabs and spaces ambiguously.
+    let tab_policy = match args.iter().position(|arg| arg == "-tt") {
+        Some(index) => {
+            args.remove(index);
+            TabPolicy::Strict
+        }
+        None => TabPolicy::Expand,
+    };
+
     match args.len() {
         2.. => {
-            eprintln!("Usage: mamushi [path/to/script]?");
+            eprintln!("Usage: mamushi [-tt] [path/to/script]?");
             exit(64);
         }
-        1 => {
-            if let Err(err) = run_file(
-                args.last()
-                    .expect("Error while reading args...")
-                    .to_string(),
-            ) {
+        1 => match run_file(
+            args.last()
+                .expect("Error while reading args...")
+                .to_string(),
+            tab_policy,
+        ) {
+            Ok(code) => exit(code),
+            Err(err) => {
                 eprintln!("Error while running file: {err}");
                 exit(1);
             }
-        }
+        },
         0 => {
-            let mut repl = Repl::new();
+            let mut repl = Repl::new(tab_policy);
             if let Err(err) = repl.run_repl() {
                 eprintln!("REPL error: {err}");
                 exit(1);
@@ -0,0 +1,704 @@
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    io::{BufRead, stdin},
+    rc::Rc,
+};
+
+use crate::core::{bigint::BigInt, token::TokenType};
+
+use super::{
+    dict::Dict,
+    format::format_float,
+    interpreter::{ExecResult, Interpreter, error, sequence_len},
+    ops,
+    value::{Namespace, TypeKind, Value, compare, new_namespace},
+};
+
+pub type BuiltinFn = fn(&mut Interpreter, Vec<Value>) -> ExecResult<Value>;
+
+pub struct Builtin {
+    pub name: &'static str,
+    pub func: BuiltinFn,
+}
+
+impl Builtin {
+    const fn new(name: &'static str, func: BuiltinFn) -> Self {
+        Self { name, func }
+    }
+}
+
+const EXCEPTIONS: &[&str] = &[
+    "AttributeError",
+    "EOFError",
+    "IOError",
+    "ImportError",
+    "IndexError",
+    "KeyError",
+    "KeyboardInterrupt",
+    "MemoryError",
+    "NameError",
+    "OverflowError",
+    "RuntimeError",
+    "SyntaxError",
+    "SystemError",
+    "SystemExit",
+    "TypeError",
+    "ValueError",
+    "ZeroDivisionError",
+];
+
+static FUNCTIONS: &[Builtin] = &[
+    Builtin::new("abs", builtin_abs),
+    Builtin::new("chr", builtin_chr),
+    Builtin::new("cmp", builtin_cmp),
+    Builtin::new("divmod", builtin_divmod),
+    Builtin::new("float", builtin_float),
+    Builtin::new("getattr", builtin_getattr),
+    Builtin::new("hasattr", builtin_hasattr),
+    Builtin::new("hex", builtin_hex),
+    Builtin::new("id", builtin_id),
+    Builtin::new("int", builtin_int),
+    Builtin::new("len", builtin_len),
+    Builtin::new("long", builtin_long),
+    Builtin::new("max", builtin_max),
+    Builtin::new("min", builtin_min),
+    Builtin::new("oct", builtin_oct),
+    Builtin::new("ord", builtin_ord),
+    Builtin::new("pow", builtin_pow),
+    Builtin::new("range", builtin_range),
+    Builtin::new("raw_input", builtin_raw_input),
+    Builtin::new("repr", builtin_repr),
+    Builtin::new("round", builtin_round),
+    Builtin::new("setattr", builtin_setattr),
+    Builtin::new("str", builtin_str),
+    Builtin::new("type", builtin_type),
+];
+
+static LIST_METHODS: &[Builtin] = &[
+    Builtin::new("append", list_append),
+    Builtin::new("count", list_count),
+    Builtin::new("index", list_index),
+    Builtin::new("insert", list_insert),
+    Builtin::new("remove", list_remove),
+    Builtin::new("reverse", list_reverse),
+    Builtin::new("sort", list_sort),
+];
+
+static DICT_METHODS: &[Builtin] = &[
+    Builtin::new("clear", dict_clear),
+    Builtin::new("copy", dict_copy),
+    Builtin::new("get", dict_get),
+    Builtin::new("has_key", dict_has_key),
+    Builtin::new("items", dict_items),
+    Builtin::new("keys", dict_keys),
+    Builtin::new("update", dict_update),
+    Builtin::new("values", dict_values),
+];
+
+pub fn namespace() -> Namespace {
+    let namespace = new_namespace();
+
+    {
+        let mut dict = namespace.borrow_mut();
+        dict.insert("None".to_string(), Value::None);
+        for builtin in FUNCTIONS {
+            dict.insert(builtin.name.to_string(), Value::Builtin(builtin));
+        }
+        for name in EXCEPTIONS {
+            dict.insert(name.to_string(), Value::str(name));
+        }
+    }
+
+    namespace
+}
+
+pub fn module(name: &str) -> Option<Namespace> {
+    match name {
+        "types" => {
+            let namespace = new_namespace();
+            {
+                let mut dict = namespace.borrow_mut();
+                for kind in TypeKind::ALL {
+                    dict.insert(types_name(kind).to_string(), Value::Type(kind));
+                }
+                dict.insert("LambdaType".to_string(), Value::Type(TypeKind::Function));
+            }
+            Some(namespace)
+        }
+        _ => None,
+    }
+}
+
+fn types_name(kind: TypeKind) -> &'static str {
+    match kind {
+        TypeKind::None => "NoneType",
+        TypeKind::Int => "IntType",
+        TypeKind::Long => "LongType",
+        TypeKind::Float => "FloatType",
+        TypeKind::String => "StringType",
+        TypeKind::Tuple => "TupleType",
+        TypeKind::List => "ListType",
+        TypeKind::Dict => "DictType",
+        TypeKind::Function => "FunctionType",
+        TypeKind::Builtin => "BuiltinFunctionType",
+        TypeKind::Class => "ClassType",
+        TypeKind::Instance => "InstanceType",
+        TypeKind::Method => "MethodType",
+        TypeKind::Module => "ModuleType",
+        TypeKind::Type => "TypeType",
+    }
+}
+
+pub fn method(receiver: &Value, name: &str) -> Option<&'static Builtin> {
+    let table = match receiver {
+        Value::List(_) => LIST_METHODS,
+        Value::Dict(_) => DICT_METHODS,
+        _ => return None,
+    };
+
+    table.iter().find(|builtin| builtin.name == name)
+}
+
+fn check_args(name: &str, args: &[Value], min: usize, max: usize) -> ExecResult {
+    if args.len() < min || args.len() > max {
+        return error(
+            "TypeError",
+            format!(
+                "{name}() requires {min} to {max} arguments, {} given",
+                args.len()
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn exact_args<const N: usize>(name: &str, args: Vec<Value>) -> ExecResult<[Value; N]> {
+    let given = args.len();
+    args.try_into().or_else(|_| {
+        error(
+            "TypeError",
+            format!(
+                "{name}() takes exactly {N} argument{} ({given} given)",
+                if N == 1 { "" } else { "s" }
+            ),
+        )
+    })
+}
+
+fn int_arg(name: &str, value: &Value) -> ExecResult<i64> {
+    match value {
+        Value::Int(i) => Ok(*i),
+        _ => error(
+            "TypeError",
+            format!("{name}() requires an integer argument"),
+        ),
+    }
+}
+
+fn str_arg<'v>(name: &str, value: &'v Value) -> ExecResult<&'v [u8]> {
+    match value {
+        Value::Str(s) => Ok(s),
+        _ => error("TypeError", format!("{name}() requires a string argument")),
+    }
+}
+
+fn sequence_items(value: &Value) -> ExecResult<Vec<Value>> {
+    match value {
+        Value::Tuple(items) => Ok(items.to_vec()),
+        Value::List(items) => Ok(items.borrow().clone()),
+        Value::Str(s) => Ok(s.iter().map(|&byte| Value::bytes([byte])).collect()),
+        _ => error("TypeError", "argument is not a sequence"),
+    }
+}
+
+fn builtin_abs(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("abs", args)?;
+    match value {
+        Value::Int(i) => match i.checked_abs() {
+            Some(result) => Ok(Value::Int(result)),
+            None => Ok(ops::long(BigInt::from_i64(i).abs())),
+        },
+        Value::Long(n) => Ok(ops::long(n.abs())),
+        Value::Float(f) => Ok(Value::Float(f.abs())),
+        _ => error("TypeError", "bad operand type for abs()"),
+    }
+}
+
+fn builtin_chr(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("chr", args)?;
+    let code = int_arg("chr", &value)?;
+    match u8::try_from(code) {
+        Ok(byte) => Ok(Value::bytes([byte])),
+        Err(_) => error("ValueError", "chr() arg not in range(256)"),
+    }
+}
+
+fn builtin_cmp(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [a, b] = exact_args("cmp", args)?;
+    Ok(Value::Int(compare(&a, &b) as i64))
+}
+
+fn builtin_divmod(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [a, b] = exact_args("divmod", args)?;
+    let div = match (&a, &b) {
+        (Value::Int(_) | Value::Long(_), Value::Int(_) | Value::Long(_)) => {
+            ops::binary(TokenType::Slash, &a, &b)?
+        }
+        _ => {
+            let quotient = ops::binary(TokenType::Slash, &a, &b)?;
+            Value::Float(ops::to_float(&quotient).floor())
+        }
+    };
+    let rem = ops::binary(TokenType::Modulo, &a, &b)?;
+    Ok(Value::tuple(vec![div, rem]))
+}
+
+fn builtin_float(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("float", args)?;
+    match value {
+        Value::Int(i) => Ok(Value::Float(i as f64)),
+        Value::Long(n) => match n.to_f64() {
+            f if f.is_finite() => Ok(Value::Float(f)),
+            _ => error("OverflowError", "long int too long to convert"),
+        },
+        Value::Float(f) => Ok(Value::Float(f)),
+        Value::Str(s) => {
+            let text = String::from_utf8_lossy(&s);
+            match text.trim().parse::<f64>() {
+                Ok(f) => Ok(Value::Float(f)),
+                Err(_) => error("ValueError", format!("float() literal too large: {text}")),
+            }
+        }
+        _ => error("TypeError", "float() argument must be a number"),
+    }
+}
+
+fn builtin_getattr(interp: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [object, name] = exact_args("getattr", args)?;
+    let name = String::from_utf8_lossy(str_arg("getattr", &name)?);
+    interp.get_attribute(&object, &name)
+}
+
+fn builtin_hasattr(interp: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [object, name] = exact_args("hasattr", args)?;
+    let name = String::from_utf8_lossy(str_arg("hasattr", &name)?);
+    Ok(Value::bool(interp.get_attribute(&object, &name).is_ok()))
+}
+
+fn builtin_hex(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("hex", args)?;
+    if let Value::Long(n) = &value {
+        let sign = if n.is_negative() { "-" } else { "" };
+        return Ok(Value::str(&format!(
+            "{sign}0x{}L",
+            n.abs().to_str_radix(16)
+        )));
+    }
+    let i = int_arg("hex", &value)?;
+    let sign = if i < 0 { "-" } else { "" };
+    Ok(Value::str(&format!("{sign}0x{:x}", i.unsigned_abs())))
+}
+
+fn builtin_id(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("id", args)?;
+    let id = value.id();
+    Ok(match i64::try_from(id) {
+        Ok(id) => Value::Int(id),
+        Err(_) => ops::long(BigInt::from_i128(id)),
+    })
+}
+
+fn builtin_int(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("int", args)?;
+    match value {
+        Value::Int(i) => Ok(Value::Int(i)),
+        Value::Long(n) => match n.to_i64() {
+            Some(i) => Ok(Value::Int(i)),
+            None => error("OverflowError", "long int too long to convert"),
+        },
+        Value::Float(f) => {
+            if f.is_finite() && f.abs() < 9.223_372_036_854_776e18 {
+                Ok(Value::Int(f.trunc() as i64))
+            } else {
+                error("OverflowError", "float too large to convert")
+            }
+        }
+        Value::Str(s) => {
+            let text = String::from_utf8_lossy(&s);
+            match text.trim().parse::<i64>() {
+                Ok(i) => Ok(Value::Int(i)),
+                Err(_) => error("ValueError", format!("invalid literal for int(): {text}")),
+            }
+        }
+        _ => error("TypeError", "int() argument must be a number"),
+    }
+}
+
+fn builtin_len(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("len", args)?;
+    match value {
+        Value::Str(_) | Value::Tuple(_) | Value::List(_) | Value::Dict(_) => {
+            Ok(Value::Int(sequence_len(&value) as i64))
+        }
+        _ => error("TypeError", "len() of unsized object"),
+    }
+}
+
+fn builtin_long(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("long", args)?;
+    match value {
+        Value::Int(_) | Value::Long(_) => Ok(ops::long(ops::to_long(&value))),
+        Value::Float(f) => match BigInt::from_f64(f) {
+            Some(n) => Ok(ops::long(n)),
+            None => error("OverflowError", "float too large to convert"),
+        },
+        Value::Str(s) => {
+            let text = String::from_utf8_lossy(&s);
+            match BigInt::from_str_radix(text.trim(), 10) {
+                Some(n) => Ok(ops::long(n)),
+                None => error("ValueError", format!("invalid literal for long(): {text}")),
+            }
+        }
+        _ => error("TypeError", "long() argument must be a number"),
+    }
+}
+
+fn extreme(name: &str, args: Vec<Value>, wanted: Ordering) -> ExecResult<Value> {
+    let items = match args.len() {
+        0 => {
+            return error(
+                "TypeError",
+                format!("{name}() requires at least 1 argument"),
+            );
+        }
+        1 => sequence_items(&args[0])?,
+        _ => args,
+    };
+
+    let mut items = items.into_iter();
+    let Some(mut best) = items.next() else {
+        return error("ValueError", format!("{name}() arg is an empty sequence"));
+    };
+
+    for item in items {
+        if compare(&item, &best) == wanted {
+            best = item;
+        }
+    }
+
+    Ok(best)
+}
+
+fn builtin_max(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    extreme("max", args, Ordering::Greater)
+}
+
+fn builtin_min(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    extreme("min", args, Ordering::Less)
+}
+
+fn builtin_oct(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("oct", args)?;
+    if let Value::Long(n) = &value {
+        let sign = if n.is_negative() { "-" } else { "" };
+        let text = if n.is_zero() {
+            "0L".to_string()
+        } else {
+            format!("{sign}0{}L", n.abs().to_str_radix(8))
+        };
+        return Ok(Value::str(&text));
+    }
+    let i = int_arg("oct", &value)?;
+    let text = match i {
+        0 => "0".to_string(),
+        i if i < 0 => format!("-0{:o}", i.unsigned_abs()),
+        i => format!("0{i:o}"),
+    };
+    Ok(Value::str(&text))
+}
+
+fn builtin_ord(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("ord", args)?;
+    match str_arg("ord", &value)? {
+        &[byte] => Ok(Value::Int(byte as i64)),
+        _ => error("TypeError", "ord() arg must have length 1"),
+    }
+}
+
+fn builtin_pow(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [base, exponent] = exact_args("pow", args)?;
+    ops::binary(TokenType::StarStar, &base, &exponent)
+}
+
+fn builtin_range(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    check_args("range", &args, 1, 3)?;
+    let numbers = args
+        .iter()
+        .map(|arg| int_arg("range", arg))
+        .collect::<ExecResult<Vec<_>>>()?;
+
+    let (start, stop, step) = match numbers[..] {
+        [stop] => (0, stop, 1),
+        [start, stop] => (start, stop, 1),
+        [start, stop, step] => (start, stop, step),
+        _ => unreachable!(),
+    };
+
+    if step == 0 {
+        return error("ValueError", "zero step for range()");
+    }
+
+    let mut items = vec![];
+    let mut current = Some(start);
+    while let Some(value) = current
+        && ((step > 0 && value < stop) || (step < 0 && value > stop))
+    {
+        items.push(Value::Int(value));
+        current = value.checked_add(step);
+    }
+
+    Ok(Value::list(items))
+}
+
+fn builtin_raw_input(interp: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    check_args("raw_input", &args, 0, 1)?;
+    interp.flush_line()?;
+    if let Some(prompt) = args.first() {
+        interp.write(&prompt.to_bytes())?;
+    }
+
+    let mut line = vec![];
+    match stdin().lock().read_until(b'\n', &mut line) {
+        Ok(0) => error("EOFError", ""),
+        Ok(_) => {
+            while line.pop_if(|byte| matches!(byte, b'\n' | b'\r')).is_some() {}
+            Ok(Value::bytes(line))
+        }
+        Err(err) => error("IOError", err.to_string()),
+    }
+}
+
+fn builtin_repr(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("repr", args)?;
+    Ok(Value::str(&value.repr()))
+}
+
+fn builtin_round(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    check_args("round", &args, 1, 2)?;
+    let number = match &args[0] {
+        Value::Int(_) | Value::Long(_) | Value::Float(_) => ops::to_float(&args[0]),
+        _ => return error("TypeError", "round() argument must be a number"),
+    };
+    let digits = match args.get(1) {
+        Some(value) => int_arg("round", value)?,
+        None => 0,
+    };
+
+    let scale = 10f64.powi(digits as i32);
+    Ok(Value::Float((number * scale).round() / scale))
+}
+
+fn builtin_setattr(interp: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [object, name, value] = exact_args("setattr", args)?;
+    let name = String::from_utf8_lossy(str_arg("setattr", &name)?);
+    interp.set_attribute(&object, &name, value)?;
+    Ok(Value::None)
+}
+
+fn builtin_str(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("str", args)?;
+    match value {
+        Value::Float(f) => Ok(Value::str(&format_float(f))),
+        Value::Str(_) => Ok(value),
+        other => Ok(Value::bytes(other.to_bytes())),
+    }
+}
+
+fn builtin_type(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [value] = exact_args("type", args)?;
+    Ok(Value::Type(value.type_of()))
+}
+
+fn list_receiver(receiver: &Value) -> Rc<RefCell<Vec<Value>>> {
+    match receiver {
+        Value::List(items) => items.clone(),
+        _ => unreachable!("list method called without a list receiver"),
+    }
+}
+
+fn list_append(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [list, item] = exact_args("append", args)?;
+    list_receiver(&list).borrow_mut().push(item);
+    Ok(Value::None)
+}
+
+fn list_count(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [list, item] = exact_args("count", args)?;
+    let count = list_receiver(&list)
+        .borrow()
+        .iter()
+        .filter(|other| **other == item)
+        .count();
+    Ok(Value::Int(count as i64))
+}
+
+fn list_index(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [list, item] = exact_args("index", args)?;
+    let position = list_receiver(&list)
+        .borrow()
+        .iter()
+        .position(|other| *other == item);
+    match position {
+        Some(position) => Ok(Value::Int(position as i64)),
+        None => error("ValueError", "list.index(x): x not in list"),
+    }
+}
+
+fn list_insert(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [list, index, item] = exact_args("insert", args)?;
+    let items = list_receiver(&list);
+    let mut items = items.borrow_mut();
+    let len = items.len() as i64;
+    let index = int_arg("insert", &index)?;
+    let index = if index < 0 { index + len } else { index }.clamp(0, len);
+    items.insert(index as usize, item);
+    Ok(Value::None)
+}
+
+fn list_remove(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [list, item] = exact_args("remove", args)?;
+    let items = list_receiver(&list);
+    let position = items.borrow().iter().position(|other| *other == item);
+    match position {
+        Some(position) => {
+            items.borrow_mut().remove(position);
+            Ok(Value::None)
+        }
+        None => error("ValueError", "list.remove(x): x not in list"),
+    }
+}
+
+fn list_reverse(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [list] = exact_args("reverse", args)?;
+    list_receiver(&list).borrow_mut().reverse();
+    Ok(Value::None)
+}
+
+fn list_sort(interp: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    check_args("sort", &args, 1, 2)?;
+    let items = list_receiver(&args[0]);
+    let unsorted = items.borrow().clone();
+
+    let sorted = match args.get(1) {
+        Some(cmp_func) => merge_sort(unsorted, &mut |a, b| match interp
+            .call(cmp_func, vec![a.clone(), b.clone()])?
+        {
+            Value::Int(result) => Ok(result.cmp(&0)),
+            _ => error("TypeError", "comparison function must return int"),
+        })?,
+        None => merge_sort(unsorted, &mut |a, b| Ok(compare(a, b)))?,
+    };
+
+    *items.borrow_mut() = sorted;
+    Ok(Value::None)
+}
+
+// A stable merge sort that, unlike the standard library's sorts, tolerates a
+// comparison function that is not a consistent total order, and stops at the
+// first error it raises.
+fn merge_sort(
+    mut items: Vec<Value>,
+    cmp: &mut dyn FnMut(&Value, &Value) -> ExecResult<Ordering>,
+) -> ExecResult<Vec<Value>> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+
+    let right = items.split_off(items.len() / 2);
+    let mut left = merge_sort(items, cmp)?.into_iter().peekable();
+    let mut right = merge_sort(right, cmp)?.into_iter().peekable();
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        let next = if cmp(a, b)? == Ordering::Greater {
+            right.next()
+        } else {
+            left.next()
+        };
+        merged.extend(next);
+    }
+    merged.extend(left);
+    merged.extend(right);
+
+    Ok(merged)
+}
+
+fn dict_receiver(receiver: &Value) -> Rc<RefCell<Dict>> {
+    match receiver {
+        Value::Dict(dict) => dict.clone(),
+        _ => unreachable!("dict method called without a dict receiver"),
+    }
+}
+
+fn dict_clear(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [dict] = exact_args("clear", args)?;
+    *dict_receiver(&dict).borrow_mut() = Dict::new();
+    Ok(Value::None)
+}
+
+fn dict_copy(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [dict] = exact_args("copy", args)?;
+    let copy = dict_receiver(&dict).borrow().clone();
+    Ok(Value::dict(copy))
+}
+
+fn dict_get(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    check_args("get", &args, 2, 3)?;
+    let found = dict_receiver(&args[0]).borrow().get(&args[1])?;
+    Ok(found
+        .or_else(|| args.get(2).cloned())
+        .unwrap_or(Value::None))
+}
+
+fn dict_has_key(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [dict, key] = exact_args("has_key", args)?;
+    let found = dict_receiver(&dict).borrow().contains_key(&key)?;
+    Ok(Value::bool(found))
+}
+
+fn dict_items(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [dict] = exact_args("items", args)?;
+    let items = dict_receiver(&dict)
+        .borrow()
+        .items()
+        .into_iter()
+        .map(|(key, value)| Value::tuple(vec![key, value]))
+        .collect();
+    Ok(Value::list(items))
+}
+
+fn dict_keys(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [dict] = exact_args("keys", args)?;
+    let keys = dict_receiver(&dict).borrow().keys();
+    Ok(Value::list(keys))
+}
+
+fn dict_update(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [dict, other] = exact_args("update", args)?;
+    let Value::Dict(other) = other else {
+        return error("TypeError", "dict.update() requires a dictionary argument");
+    };
+    let items = other.borrow().items();
+    let target = dict_receiver(&dict);
+    for (key, value) in items {
+        target.borrow_mut().insert(key, value)?;
+    }
+    Ok(Value::None)
+}
+
+fn dict_values(_: &mut Interpreter, args: Vec<Value>) -> ExecResult<Value> {
+    let [dict] = exact_args("values", args)?;
+    let values = dict_receiver(&dict).borrow().values();
+    Ok(Value::list(values))
+}
@@ -0,0 +1,140 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::core::bigint::BigInt;
+
+use super::{
+    interpreter::{ExecResult, error},
+    value::Value,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum HashKey {
+    None,
+    Int(i64),
+    Long(BigInt),
+    Float(u64),
+    Str(Rc<[u8]>),
+    Tuple(Vec<HashKey>),
+    Identity(i128),
+}
+
+fn hash_key(value: &Value) -> ExecResult<HashKey> {
+    match value {
+        Value::None => Ok(HashKey::None),
+        Value::Int(i) => Ok(HashKey::Int(*i)),
+        Value::Long(n) => Ok(integer_key(n.as_ref().clone())),
+        Value::Float(f) => {
+            // Equal numbers must hash alike, so integral floats share the
+            // integer key.
+            match BigInt::from_f64(*f) {
+                Some(n) if f.fract() == 0.0 => Ok(integer_key(n)),
+                _ => Ok(HashKey::Float(f.to_bits())),
+            }
+        }
+        Value::Str(s) => Ok(HashKey::Str(s.clone())),
+        Value::Tuple(items) => Ok(HashKey::Tuple(
+            items.iter().map(hash_key).collect::<ExecResult<_>>()?,
+        )),
+        Value::List(_) | Value::Dict(_) => error("TypeError", "unhashable type"),
+        _ => Ok(HashKey::Identity(value.id())),
+    }
+}
+
+fn integer_key(n: BigInt) -> HashKey {
+    match n.to_i64() {
+        Some(i) => HashKey::Int(i),
+        None => HashKey::Long(n),
+    }
+}
+
+/// An insertion-ordered hash table keyed by hashable runtime values.
+/// Removed entries leave a `None` tombstone, which keeps deletion constant
+/// time; the entries are compacted once tombstones make up half of them.
+#[derive(Clone, Default)]
+pub struct Dict {
+    entries: Vec<Option<(Value, Value)>>,
+    index: HashMap<HashKey, usize>,
+}
+
+impl Dict {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn get(&self, key: &Value) -> ExecResult<Option<Value>> {
+        let slot = self.index.get(&hash_key(key)?);
+        Ok(slot.and_then(|&slot| self.entries[slot].as_ref().map(|(_, value)| value.clone())))
+    }
+
+    pub fn contains_key(&self, key: &Value) -> ExecResult<bool> {
+        Ok(self.index.contains_key(&hash_key(key)?))
+    }
+
+    pub fn insert(&mut self, key: Value, value: Value) -> ExecResult {
+        let hashed = hash_key(&key)?;
+
+        match self.index.get(&hashed) {
+            Some(&slot) => {
+                if let Some(entry) = &mut self.entries[slot] {
+                    entry.1 = value;
+                }
+            }
+            None => {
+                self.index.insert(hashed, self.entries.len());
+                self.entries.push(Some((key, value)));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &Value) -> ExecResult<Option<Value>> {
+        let Some(slot) = self.index.remove(&hash_key(key)?) else {
+            return Ok(None);
+        };
+
+        let removed = self.entries[slot].take().map(|(_, value)| value);
+        if self.index.len() * 2 < self.entries.len() {
+            self.compact()?;
+        }
+
+        Ok(removed)
+    }
+
+    fn compact(&mut self) -> ExecResult {
+        self.entries.retain(Option::is_some);
+        for (slot, (key, _)) in self.entries.iter().flatten().enumerate() {
+            self.index.insert(hash_key(key)?, slot);
+        }
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Value, &Value)> {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key, value))
+    }
+
+    pub fn keys(&self) -> Vec<Value> {
+        self.iter().map(|(key, _)| key.clone()).collect()
+    }
+
+    pub fn values(&self) -> Vec<Value> {
+        self.iter().map(|(_, value)| value.clone()).collect()
+    }
+
+    pub fn items(&self) -> Vec<(Value, Value)> {
+        self.iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
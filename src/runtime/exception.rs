@@ -0,0 +1,95 @@
+use std::{fmt, rc::Rc};
+
+use crate::{core::token::Span, diagnostics::source::SourceFile};
+
+use super::value::Value;
+
+pub struct TracebackEntry {
+    pub source: Rc<SourceFile>,
+    pub span: Span,
+    pub function: String,
+}
+
+/// A raised exception. `kind` is the string or class that was raised and
+/// `value` its associated value, mirroring the `raise kind, value` form.
+#[derive(Clone)]
+pub struct Exception {
+    pub kind: Value,
+    pub value: Value,
+    pub traceback: Vec<Rc<TracebackEntry>>,
+    pub span: Option<Span>,
+}
+
+impl Exception {
+    pub fn new(kind: Value, value: Value) -> Self {
+        Self {
+            kind,
+            value,
+            traceback: vec![],
+            span: None,
+        }
+    }
+
+    pub fn kind_name(&self) -> String {
+        match &self.kind {
+            Value::Class(class) => class.name.clone(),
+            kind => kind.to_str(),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match &self.value {
+            Value::None => String::new(),
+            value => value.to_str(),
+        }
+    }
+
+    /// The traceback as printed to stderr. It is built from bytes so that
+    /// string values come out exactly as `print` would write them.
+    pub fn traceback(&self) -> Vec<u8> {
+        let mut text = b"Traceback (innermost last):\n".to_vec();
+
+        for entry in self.traceback.iter().rev() {
+            let (line, _) = entry.source.location(entry.span.0);
+            text.extend_from_slice(
+                format!(
+                    "  File \"{}\", line {line}, in {}\n    {}\n",
+                    entry.source.name,
+                    entry.function,
+                    entry.source.line(line).trim()
+                )
+                .as_bytes(),
+            );
+        }
+
+        match &self.kind {
+            Value::Class(class) => text.extend_from_slice(class.name.as_bytes()),
+            kind => text.extend_from_slice(&kind.to_bytes()),
+        }
+        let message = self.value.to_bytes();
+        if !matches!(self.value, Value::None) && !message.is_empty() {
+            text.extend_from_slice(b": ");
+            text.extend_from_slice(&message);
+        }
+        text
+    }
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = self.message();
+        if message.is_empty() {
+            write!(f, "{}", self.kind_name())
+        } else {
+            write!(f, "{}: {}", self.kind_name(), message)
+        }
+    }
+}
+
+impl fmt::Debug for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::error::Error for Exception {}
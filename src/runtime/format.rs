@@ -0,0 +1,368 @@
+use std::iter::Peekable;
+
+use crate::core::bigint::BigInt;
+
+use super::value::Value;
+
+pub fn format_float(value: f64) -> String {
+    let mut text = format_general(value, 12, false);
+
+    if text.chars().all(|ch| ch.is_ascii_digit() || ch == '-') {
+        text.push_str(".0");
+    }
+
+    text
+}
+
+fn format_general(value: f64, precision: usize, alternate: bool) -> String {
+    if !value.is_finite() {
+        return format_non_finite(value);
+    }
+
+    let precision = precision.max(1);
+    let scientific = format!("{:.*e}", precision - 1, value);
+    let (_, exponent) = scientific
+        .split_once('e')
+        .expect("Error while splitting exponent...");
+    let exponent = exponent
+        .parse::<i32>()
+        .expect("Error while parsing exponent...");
+
+    if exponent < -4 || exponent >= precision as i32 {
+        let text = format_exponent(value, precision - 1);
+        if alternate {
+            return text;
+        }
+        let (mantissa, exponent) = text
+            .split_once('e')
+            .expect("Error while splitting exponent...");
+        format!("{}e{exponent}", strip_fraction_zeros(mantissa))
+    } else {
+        let decimals = (precision as i32 - 1 - exponent) as usize;
+        let text = format!("{value:.decimals$}");
+        if alternate {
+            text
+        } else {
+            strip_fraction_zeros(&text)
+        }
+    }
+}
+
+fn format_exponent(value: f64, decimals: usize) -> String {
+    if !value.is_finite() {
+        return format_non_finite(value);
+    }
+
+    let text = format!("{value:.decimals$e}");
+    let (mantissa, exponent) = text
+        .split_once('e')
+        .expect("Error while splitting exponent...");
+    let exponent = exponent
+        .parse::<i32>()
+        .expect("Error while parsing exponent...");
+    let sign = if exponent < 0 { '-' } else { '+' };
+
+    format!("{mantissa}e{sign}{:02}", exponent.abs())
+}
+
+fn format_non_finite(value: f64) -> String {
+    if value.is_nan() {
+        "nan".to_string()
+    } else if value > 0.0 {
+        "inf".to_string()
+    } else {
+        "-inf".to_string()
+    }
+}
+
+fn strip_fraction_zeros(text: &str) -> String {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[derive(Default)]
+struct Spec {
+    left_align: bool,
+    sign: bool,
+    space: bool,
+    alternate: bool,
+    zero_pad: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+pub enum FormatError {
+    TypeError(String),
+    KeyError(Value),
+}
+
+impl From<String> for FormatError {
+    fn from(message: String) -> Self {
+        FormatError::TypeError(message)
+    }
+}
+
+pub fn format_percent(template: &[u8], args: &Value) -> Result<Vec<u8>, FormatError> {
+    let (items, mapping) = match args {
+        Value::Tuple(items) => (items.to_vec(), None),
+        Value::Dict(_) => (vec![], Some(args.clone())),
+        other => (vec![other.clone()], None),
+    };
+
+    let mut out = Vec::with_capacity(template.len());
+    let mut next = 0;
+    let mut chars = template.iter().copied().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != b'%' {
+            out.push(ch);
+            continue;
+        }
+
+        let mut key_value = None;
+        if chars.peek() == Some(&b'(') {
+            chars.next();
+            let mut key = Vec::new();
+            let mut depth = 1;
+            for ch in chars.by_ref() {
+                match ch {
+                    b'(' => depth += 1,
+                    b')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                key.push(ch);
+            }
+            let Some(Value::Dict(entries)) = &mapping else {
+                return Err("format requires a mapping".to_string().into());
+            };
+            let key = Value::bytes(key);
+            let found = entries.borrow().get(&key).ok().flatten();
+            match found {
+                Some(value) => key_value = Some(value),
+                None => return Err(FormatError::KeyError(key)),
+            }
+        }
+
+        let mut spec = Spec::default();
+        while let Some(&flag) = chars.peek() {
+            match flag {
+                b'-' => spec.left_align = true,
+                b'+' => spec.sign = true,
+                b' ' => spec.space = true,
+                b'#' => spec.alternate = true,
+                b'0' => spec.zero_pad = true,
+                _ => break,
+            }
+            chars.next();
+        }
+
+        let mut take_arg = |key_value: &mut Option<Value>| -> Result<Value, String> {
+            if let Some(value) = key_value.take() {
+                return Ok(value);
+            }
+            if mapping.is_some() {
+                return Ok(args.clone());
+            }
+            let value = items
+                .get(next)
+                .cloned()
+                .ok_or_else(|| "not enough arguments for format string".to_string())?;
+            next += 1;
+            Ok(value)
+        };
+
+        if chars.peek() == Some(&b'*') {
+            chars.next();
+            match take_arg(&mut None)? {
+                Value::Int(width) => spec.width = width.max(0) as usize,
+                _ => return Err("* wants int".to_string().into()),
+            }
+        } else {
+            spec.width = read_number(&mut chars).unwrap_or(0);
+        }
+
+        if chars.peek() == Some(&b'.') {
+            chars.next();
+            if chars.peek() == Some(&b'*') {
+                chars.next();
+                match take_arg(&mut None)? {
+                    Value::Int(precision) => spec.precision = Some(precision.max(0) as usize),
+                    _ => return Err("* wants int".to_string().into()),
+                }
+            } else {
+                spec.precision = Some(read_number(&mut chars).unwrap_or(0));
+            }
+        }
+
+        while matches!(chars.peek(), Some(b'h' | b'l' | b'L')) {
+            chars.next();
+        }
+
+        let Some(conversion) = chars.next() else {
+            return Err("incomplete format".to_string().into());
+        };
+
+        if conversion == b'%' {
+            out.push(b'%');
+            continue;
+        }
+
+        let value = take_arg(&mut key_value)?;
+        let (body, numeric) = match conversion {
+            b'd' | b'i' | b'u' => (
+                format_signed(int_arg(&value)?.to_string(), &spec).into_bytes(),
+                true,
+            ),
+            b'o' => {
+                let number = int_arg(&value)?;
+                let digits = number.abs().to_str_radix(8);
+                let digits = if spec.alternate && !number.is_zero() {
+                    format!("0{digits}")
+                } else {
+                    digits
+                };
+                (
+                    format_signed(with_sign(number.is_negative(), digits), &spec).into_bytes(),
+                    true,
+                )
+            }
+            b'x' | b'X' => {
+                let number = int_arg(&value)?;
+                let mut digits = number.abs().to_str_radix(16);
+                if spec.alternate {
+                    digits = format!("0x{digits}");
+                }
+                if conversion == b'X' {
+                    digits = digits.to_uppercase();
+                }
+                (
+                    format_signed(with_sign(number.is_negative(), digits), &spec).into_bytes(),
+                    true,
+                )
+            }
+            b'e' | b'E' | b'f' | b'F' | b'g' | b'G' => {
+                let number = float_arg(&value)?;
+                let precision = spec.precision.unwrap_or(6);
+                let text = match conversion.to_ascii_lowercase() {
+                    b'e' => format_exponent(number, precision),
+                    b'f' => format!("{number:.precision$}"),
+                    _ => format_general(number, precision, spec.alternate),
+                };
+                let text = if conversion.is_ascii_uppercase() {
+                    text.to_uppercase()
+                } else {
+                    text
+                };
+                (format_signed(text, &spec).into_bytes(), true)
+            }
+            b's' | b'r' => {
+                let text = if conversion == b's' {
+                    value.to_bytes().to_vec()
+                } else {
+                    value.repr().into_bytes()
+                };
+                let text = match spec.precision {
+                    Some(precision) => text.into_iter().take(precision).collect(),
+                    None => text,
+                };
+                (text, false)
+            }
+            b'c' => match &value {
+                Value::Int(code) => (
+                    vec![u8::try_from(*code).map_err(|_| "%c arg not in range".to_string())?],
+                    false,
+                ),
+                Value::Str(s) if s.len() == 1 => (s.to_vec(), false),
+                _ => return Err("%c requires int or char".to_string().into()),
+            },
+            other => {
+                return Err(format!("unsupported format character '{}'", char::from(other)).into());
+            }
+        };
+
+        out.extend_from_slice(&pad(body, &spec, numeric));
+    }
+
+    if mapping.is_none() && next < items.len() {
+        return Err("not all arguments converted".to_string().into());
+    }
+
+    Ok(out)
+}
+
+fn read_number(chars: &mut Peekable<impl Iterator<Item = u8>>) -> Option<usize> {
+    let mut number = None;
+    while let Some(digit) = chars.peek().and_then(|&ch| char::from(ch).to_digit(10)) {
+        number = Some(number.unwrap_or(0) * 10 + digit as usize);
+        chars.next();
+    }
+    number
+}
+
+fn int_arg(value: &Value) -> Result<BigInt, String> {
+    match value {
+        Value::Int(i) => Ok(BigInt::from_i64(*i)),
+        Value::Long(n) => Ok(n.as_ref().clone()),
+        Value::Float(f) => {
+            BigInt::from_f64(*f).ok_or_else(|| "float too large to convert".to_string())
+        }
+        _ => Err("illegal argument type for built-in operation".to_string()),
+    }
+}
+
+fn float_arg(value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Int(i) => Ok(*i as f64),
+        Value::Long(n) => Ok(n.to_f64()),
+        Value::Float(f) => Ok(*f),
+        _ => Err("illegal argument type for built-in operation".to_string()),
+    }
+}
+
+fn with_sign(negative: bool, digits: String) -> String {
+    if negative {
+        format!("-{digits}")
+    } else {
+        digits
+    }
+}
+
+fn format_signed(text: String, spec: &Spec) -> String {
+    if text.starts_with('-') {
+        text
+    } else if spec.sign {
+        format!("+{text}")
+    } else if spec.space {
+        format!(" {text}")
+    } else {
+        text
+    }
+}
+
+fn pad(text: Vec<u8>, spec: &Spec, numeric: bool) -> Vec<u8> {
+    if text.len() >= spec.width {
+        return text;
+    }
+
+    let fill = spec.width - text.len();
+    if spec.left_align {
+        [text, vec![b' '; fill]].concat()
+    } else if spec.zero_pad && numeric {
+        let sign_len = text
+            .iter()
+            .take_while(|ch| matches!(ch, b'-' | b'+' | b' '))
+            .count();
+        let (sign, digits) = text.split_at(sign_len);
+        [sign, &vec![b'0'; fill], digits].concat()
+    } else {
+        [vec![b' '; fill], text].concat()
+    }
+}
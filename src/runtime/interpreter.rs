@@ -0,0 +1,1113 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read_to_string,
+    io::{self, Write},
+    path::PathBuf,
+    rc::Rc,
+};
+
+use crate::{
+    core::{
+        ast::{ExceptClause, Expr, Parameters, Stmt, StmtKind, Target},
+        token::{LiteralValue, Span, TokenType},
+    },
+    diagnostics::source::SourceFile,
+    frontend::{lexer::Lexer, parser::Parser},
+};
+
+use super::{
+    builtins,
+    dict::Dict,
+    exception::{Exception, TracebackEntry},
+    ops,
+    value::{
+        Class, Function, FunctionBody, Instance, Method, Module, Namespace, Value, new_namespace,
+    },
+};
+
+const MAX_RECURSION_DEPTH: usize = 1000;
+
+pub enum Unwind {
+    Return(Value),
+    Break,
+    Continue,
+    Error(Exception),
+}
+
+pub type ExecResult<T = ()> = Result<T, Unwind>;
+
+pub fn error<T>(kind: &str, message: impl Into<String>) -> ExecResult<T> {
+    Err(Unwind::Error(Exception::new(
+        Value::str(kind),
+        Value::str(&message.into()),
+    )))
+}
+
+pub fn key_error<T>(key: &Value) -> ExecResult<T> {
+    Err(Unwind::Error(Exception::new(
+        Value::str("KeyError"),
+        Value::bytes(key.to_bytes()),
+    )))
+}
+
+struct Frame {
+    locals: Namespace,
+    globals: Namespace,
+    global_names: HashSet<String>,
+    function: String,
+    source: Rc<SourceFile>,
+}
+
+impl Frame {
+    fn new(locals: Namespace, globals: Namespace, function: &str, source: Rc<SourceFile>) -> Self {
+        Self {
+            locals,
+            globals,
+            global_names: HashSet::new(),
+            function: function.to_string(),
+            source,
+        }
+    }
+}
+
+pub struct Interpreter {
+    builtins: Namespace,
+    frames: Vec<Frame>,
+    modules: HashMap<String, Value>,
+    search_path: Vec<PathBuf>,
+    handled: Vec<Exception>,
+    out: Box<dyn Write>,
+    // Set after a `print` item, meaning the next item needs a separating
+    // space or, if nothing follows, the line still needs ending.
+    softspace: bool,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Self::with_output(Box::new(io::stdout()))
+    }
+
+    pub fn with_output(out: Box<dyn Write>) -> Self {
+        let globals = new_namespace();
+        globals
+            .borrow_mut()
+            .insert("__name__".to_string(), Value::str("__main__"));
+
+        Self {
+            builtins: builtins::namespace(),
+            frames: vec![Frame::new(
+                globals.clone(),
+                globals,
+                "?",
+                Rc::new(SourceFile::new("<stdin>", "")),
+            )],
+            modules: HashMap::new(),
+            search_path: vec![],
+            handled: vec![],
+            out,
+            softspace: false,
+        }
+    }
+
+    pub fn add_search_path(&mut self, path: PathBuf) {
+        self.search_path.push(path);
+    }
+
+    pub fn run(&mut self, source: SourceFile, statements: &[Stmt]) -> Result<(), Exception> {
+        self.frame_mut().source = Rc::new(source);
+        let result = self.exec_block(statements);
+        let result = result.and(self.flush_line());
+        let _ = self.out.flush();
+        self.finish(result)
+    }
+
+    pub fn run_interactive(
+        &mut self,
+        source: SourceFile,
+        statements: &[Stmt],
+    ) -> Result<(), Exception> {
+        self.frame_mut().source = Rc::new(source);
+
+        for stmt in statements {
+            let result = match &stmt.kind {
+                StmtKind::Expression(expr) => self.eval(expr).and_then(|value| {
+                    if matches!(value, Value::None) {
+                        return Ok(());
+                    }
+                    let repr = value.repr();
+                    self.write_line(repr.as_bytes())
+                }),
+                _ => self.exec(stmt),
+            }
+            .map_err(|unwind| at_statement(unwind, stmt));
+            let result = result.and(self.flush_line());
+            let _ = self.out.flush();
+            self.finish(result)?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, result: ExecResult) -> Result<(), Exception> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(Unwind::Error(exc)) => Err(self.traceback_entry(exc)),
+            Err(Unwind::Return(_)) => Err(syntax_error("'return' outside function")),
+            Err(Unwind::Break) => Err(syntax_error("'break' outside loop")),
+            Err(Unwind::Continue) => Err(syntax_error("'continue' not properly in loop")),
+        }
+    }
+
+    fn frame(&self) -> &Frame {
+        self.frames
+            .last()
+            .expect("Error while reading frame stack...")
+    }
+
+    fn frame_mut(&mut self) -> &mut Frame {
+        self.frames
+            .last_mut()
+            .expect("Error while reading frame stack...")
+    }
+
+    fn with_frame<T>(
+        &mut self,
+        frame: Frame,
+        run: impl FnOnce(&mut Self) -> ExecResult<T>,
+    ) -> ExecResult<T> {
+        self.frames.push(frame);
+        let result = run(self).map_err(|unwind| match unwind {
+            Unwind::Error(exc) => Unwind::Error(self.traceback_entry(exc)),
+            other => other,
+        });
+        self.frames.pop();
+        result
+    }
+
+    // Records the current frame in the traceback of an exception leaving it,
+    // pointing at the statement that was executing when it was raised.
+    fn traceback_entry(&self, mut exc: Exception) -> Exception {
+        if let Some(span) = exc.span.take() {
+            let frame = self.frame();
+            exc.traceback.push(Rc::new(TracebackEntry {
+                source: frame.source.clone(),
+                span,
+                function: frame.function.clone(),
+            }));
+        }
+        exc
+    }
+
+    pub fn write_line(&mut self, text: &[u8]) -> ExecResult {
+        self.softspace = false;
+        self.out
+            .write_all(text)
+            .and_then(|_| self.out.write_all(b"\n"))
+            .or_else(|err| error("IOError", err.to_string()))
+    }
+
+    pub fn write(&mut self, text: &[u8]) -> ExecResult {
+        self.softspace = false;
+        self.out
+            .write_all(text)
+            .and_then(|_| self.out.flush())
+            .or_else(|err| error("IOError", err.to_string()))
+    }
+
+    /// Ends a line left open by a `print` with a trailing comma.
+    pub fn flush_line(&mut self) -> ExecResult {
+        if self.softspace {
+            self.write_line(b"")?;
+        }
+        Ok(())
+    }
+
+    fn print_item(&mut self, text: &[u8]) -> ExecResult {
+        let separator: &[u8] = if self.softspace { b" " } else { b"" };
+        self.out
+            .write_all(&[separator, text].concat())
+            .or_else(|err| error("IOError", err.to_string()))?;
+
+        // Text already ending in a newline or tab needs no separating space.
+        self.softspace = !text
+            .last()
+            .is_some_and(|&byte| byte.is_ascii_whitespace() && byte != b' ');
+        Ok(())
+    }
+
+    fn exec_block(&mut self, statements: &[Stmt]) -> ExecResult {
+        for stmt in statements {
+            self.exec(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn exec(&mut self, stmt: &Stmt) -> ExecResult {
+        self.exec_kind(&stmt.kind)
+            .map_err(|unwind| at_statement(unwind, stmt))
+    }
+
+    fn exec_kind(&mut self, stmt: &StmtKind) -> ExecResult {
+        match stmt {
+            StmtKind::FunctionDef { name, params, body } => {
+                let function = Function {
+                    name: name.clone(),
+                    params: params.clone(),
+                    defaults: self.eval_defaults(params)?,
+                    body: FunctionBody::Block(Rc::new(body.clone())),
+                    globals: self.frame().globals.clone(),
+                    source: self.frame().source.clone(),
+                };
+                self.store(name, Value::Function(Rc::new(function)));
+            }
+            StmtKind::ClassDef { name, base, body } => {
+                let bases = match base {
+                    Some(expr) => match self.eval(expr)? {
+                        Value::Class(class) => vec![class],
+                        Value::Tuple(items) => items
+                            .iter()
+                            .map(|item| match item {
+                                Value::Class(class) => Ok(class.clone()),
+                                _ => error("TypeError", "base is not a class object"),
+                            })
+                            .collect::<ExecResult<Vec<_>>>()?,
+                        _ => return error("TypeError", "base is not a class object"),
+                    },
+                    None => vec![],
+                };
+
+                let dict = new_namespace();
+                let frame = Frame::new(
+                    dict.clone(),
+                    self.frame().globals.clone(),
+                    name,
+                    self.frame().source.clone(),
+                );
+                self.with_frame(frame, |interp| interp.exec_block(body))?;
+
+                let class = Class {
+                    name: name.clone(),
+                    bases,
+                    dict,
+                };
+                self.store(name, Value::Class(Rc::new(class)));
+            }
+            StmtKind::Return(expr) => {
+                let value = match expr {
+                    Some(expr) => self.eval(expr)?,
+                    None => Value::None,
+                };
+                return Err(Unwind::Return(value));
+            }
+            StmtKind::Expression(expr) => {
+                self.eval(expr)?;
+            }
+            StmtKind::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if self.eval(condition)?.is_truthy() {
+                    self.exec_block(then_branch)?;
+                } else if let Some(else_branch) = else_branch {
+                    self.exec_block(else_branch)?;
+                }
+            }
+            StmtKind::While {
+                condition,
+                body,
+                else_branch,
+            } => {
+                while self.eval(condition)?.is_truthy() {
+                    match self.exec_block(body) {
+                        Err(Unwind::Break) => return Ok(()),
+                        Err(Unwind::Continue) | Ok(()) => {}
+                        Err(other) => return Err(other),
+                    }
+                }
+
+                if let Some(else_branch) = else_branch {
+                    self.exec_block(else_branch)?;
+                }
+            }
+            StmtKind::Print {
+                items,
+                trailing_comma,
+            } => {
+                for item in items {
+                    let text = self.eval(item)?.to_bytes();
+                    self.print_item(&text)?;
+                }
+
+                if !trailing_comma {
+                    self.write_line(b"")?;
+                }
+            }
+            StmtKind::Assign { target, value } => {
+                let value = self.eval(value)?;
+                self.assign(target, value)?;
+            }
+            StmtKind::For {
+                target,
+                iterable,
+                body,
+                else_branch,
+            } => {
+                let sequence = self.eval(iterable)?;
+                let mut index = 0;
+
+                while let Some(item) = self.sequence_item(&sequence, index)? {
+                    index += 1;
+                    self.assign(target, item)?;
+                    match self.exec_block(body) {
+                        Err(Unwind::Break) => return Ok(()),
+                        Err(Unwind::Continue) | Ok(()) => {}
+                        Err(other) => return Err(other),
+                    }
+                }
+
+                if let Some(else_branch) = else_branch {
+                    self.exec_block(else_branch)?;
+                }
+            }
+            StmtKind::Block(statements) => self.exec_block(statements)?,
+            StmtKind::Import(names) => {
+                for name in names {
+                    let module = self.import(name)?;
+                    self.store(name, module);
+                }
+            }
+            StmtKind::FromImport { module, names } => {
+                let Value::Module(module) = self.import(module)? else {
+                    return error("ImportError", format!("No module named {module}"));
+                };
+
+                for name in names {
+                    let value = module.dict.borrow().get(name).cloned();
+                    match value {
+                        Some(value) => self.store(name, value),
+                        None => {
+                            return error("ImportError", format!("cannot import name {name}"));
+                        }
+                    }
+                }
+            }
+            StmtKind::Global(names) => {
+                self.frame_mut().global_names.extend(names.iter().cloned());
+            }
+            StmtKind::Try {
+                body,
+                except_clauses,
+                finally_body,
+            } => {
+                let result = self.exec_try(body, except_clauses);
+
+                // The finally block runs however the try left, and only
+                // replaces that outcome if it unwinds itself.
+                if let Some(finally_body) = finally_body {
+                    self.exec_block(finally_body)?;
+                }
+
+                return result;
+            }
+            StmtKind::Raise { exception, value } => {
+                let Some(exception) = exception else {
+                    return match self.handled.last() {
+                        Some(exc) => Err(Unwind::Error(exc.clone())),
+                        None => error(
+                            "TypeError",
+                            "exceptions must be strings, classes, or instances",
+                        ),
+                    };
+                };
+
+                let kind = self.eval(exception)?;
+                let value = match value {
+                    Some(value) => Some(self.eval(value)?),
+                    None => None,
+                };
+                let exc = self.new_exception(kind, value)?;
+                return Err(Unwind::Error(exc));
+            }
+            StmtKind::Del(target) => self.delete(target)?,
+            StmtKind::Pass => {}
+            StmtKind::Break => return Err(Unwind::Break),
+            StmtKind::Continue => return Err(Unwind::Continue),
+        }
+
+        Ok(())
+    }
+
+    fn exec_try(&mut self, body: &[Stmt], except_clauses: &[ExceptClause]) -> ExecResult {
+        let exc = match self.exec_block(body) {
+            Err(Unwind::Error(exc)) => exc,
+            other => return other,
+        };
+
+        for clause in except_clauses {
+            let matched = match &clause.matcher {
+                Some(expr) => {
+                    let matcher = self.eval(expr)?;
+                    exception_matches(&exc, &matcher)
+                }
+                None => true,
+            };
+
+            if matched {
+                if let Some(target) = &clause.target {
+                    self.assign(target, exc.value.clone())?;
+                }
+
+                self.handled.push(exc);
+                let result = self.exec_block(&clause.body);
+                self.handled.pop();
+                return result;
+            }
+        }
+
+        Err(Unwind::Error(exc))
+    }
+
+    fn new_exception(&mut self, kind: Value, value: Option<Value>) -> ExecResult<Exception> {
+        match kind {
+            Value::Str(_) => Ok(Exception::new(kind, value.unwrap_or(Value::None))),
+            Value::Class(ref class) => {
+                let instance = match value {
+                    Some(Value::Instance(instance)) if instance.class.is_subclass(class) => {
+                        Value::Instance(instance)
+                    }
+                    Some(Value::Tuple(args)) => self.call(&kind, args.to_vec())?,
+                    Some(value) => self.call(&kind, vec![value])?,
+                    None => self.call(&kind, vec![])?,
+                };
+                let Value::Instance(ref raised) = instance else {
+                    unreachable!("Error while instantiating exception class...");
+                };
+                Ok(Exception::new(Value::Class(raised.class.clone()), instance))
+            }
+            Value::Instance(ref instance) => {
+                if value.is_some() {
+                    return error(
+                        "TypeError",
+                        "instance exception may not have a separate value",
+                    );
+                }
+                Ok(Exception::new(Value::Class(instance.class.clone()), kind))
+            }
+            _ => error(
+                "TypeError",
+                "exceptions must be strings, classes, or instances",
+            ),
+        }
+    }
+
+    fn eval(&mut self, expr: &Expr) -> ExecResult<Value> {
+        match expr {
+            Expr::Literal(literal) => match literal {
+                LiteralValue::Int(i) => Ok(Value::Int(*i)),
+                LiteralValue::Long(n) => Ok(Value::Long(Rc::new(n.clone()))),
+                LiteralValue::Float(f) => Ok(Value::Float(*f)),
+                LiteralValue::String(s) => Ok(Value::bytes(s.as_slice())),
+                LiteralValue::Identifier(name) => self.lookup(name),
+            },
+            Expr::Variable(name) => self.lookup(name),
+            Expr::Unary { op, expr } => {
+                let value = self.eval(expr)?;
+                match op {
+                    TokenType::Not => Ok(Value::bool(!value.is_truthy())),
+                    TokenType::Minus => ops::negate(&value),
+                    TokenType::Tilde => ops::invert(&value),
+                    _ => error("SystemError", format!("bad unary operator {op}")),
+                }
+            }
+            Expr::Binary { left, op, right } => {
+                let left = self.eval(left)?;
+
+                match op {
+                    TokenType::And if !left.is_truthy() => return Ok(left),
+                    TokenType::Or if left.is_truthy() => return Ok(left),
+                    TokenType::And | TokenType::Or => return self.eval(right),
+                    _ => {}
+                }
+
+                let right = self.eval(right)?;
+                ops::binary(*op, &left, &right)
+            }
+            Expr::Compare {
+                left,
+                ops,
+                comparators,
+            } => {
+                // Each operand is evaluated once, and evaluation stops at the
+                // first comparison that fails.
+                let mut left = self.eval(left)?;
+                for (op, comparator) in ops.iter().zip(comparators) {
+                    let right = self.eval(comparator)?;
+                    if !ops::compare_op(*op, &left, &right)? {
+                        return Ok(Value::bool(false));
+                    }
+                    left = right;
+                }
+                Ok(Value::bool(true))
+            }
+            Expr::Grouping(expr) => self.eval(expr),
+            Expr::Call { callee, args } => {
+                let callee = self.eval(callee)?;
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<ExecResult<Vec<_>>>()?;
+                self.call(&callee, args)
+            }
+            Expr::Tuple(items) => Ok(Value::tuple(self.eval_all(items)?)),
+            Expr::List(items) => Ok(Value::list(self.eval_all(items)?)),
+            Expr::Dict(pairs) => {
+                let mut dict = Dict::new();
+                for (key, value) in pairs {
+                    let key = self.eval(key)?;
+                    let value = self.eval(value)?;
+                    dict.insert(key, value)?;
+                }
+                Ok(Value::dict(dict))
+            }
+            Expr::Get { object, name } => {
+                let object = self.eval(object)?;
+                self.get_attribute(&object, name)
+            }
+            Expr::Set {
+                object,
+                name,
+                value,
+            } => {
+                let object = self.eval(object)?;
+                let value = self.eval(value)?;
+                self.set_attribute(&object, name, value.clone())?;
+                Ok(value)
+            }
+            Expr::Lambda { params, body } => {
+                let function = Function {
+                    name: "<lambda>".to_string(),
+                    params: params.clone(),
+                    defaults: self.eval_defaults(params)?,
+                    body: FunctionBody::Lambda(Rc::new(body.as_ref().clone())),
+                    globals: self.frame().globals.clone(),
+                    source: self.frame().source.clone(),
+                };
+                Ok(Value::Function(Rc::new(function)))
+            }
+            Expr::Index { object, index } => {
+                let object = self.eval(object)?;
+                let index = self.eval(index)?;
+                self.get_item(&object, &index)
+            }
+            Expr::Slice {
+                object,
+                lower,
+                upper,
+            } => {
+                let object = self.eval(object)?;
+                let (lower, upper) = self.slice_bounds(&object, lower, upper)?;
+                slice(&object, lower, upper)
+            }
+        }
+    }
+
+    // Slice bounds default to the whole sequence, count from the end when
+    // negative, and are clamped to the sequence rather than raising.
+    fn slice_bounds(
+        &mut self,
+        sequence: &Value,
+        lower: &Option<Box<Expr>>,
+        upper: &Option<Box<Expr>>,
+    ) -> ExecResult<(usize, usize)> {
+        let mut bounds = [None, None];
+        for (bound, expr) in bounds.iter_mut().zip([lower, upper]) {
+            let Some(expr) = expr else {
+                continue;
+            };
+            let Some(i) = index_int(&self.eval(expr)?) else {
+                return error("TypeError", "slice index must be int");
+            };
+            *bound = Some(i);
+        }
+
+        // Evaluating the bounds can resize the sequence, so its length is
+        // only read once they are known.
+        let len = sequence_len(sequence) as i64;
+        let [lower, upper] = [bounds[0].unwrap_or(0), bounds[1].unwrap_or(len)]
+            .map(|i| if i < 0 { i + len } else { i }.clamp(0, len));
+        Ok((lower as usize, upper.max(lower) as usize))
+    }
+
+    fn eval_all(&mut self, exprs: &[Expr]) -> ExecResult<Vec<Value>> {
+        exprs.iter().map(|expr| self.eval(expr)).collect()
+    }
+
+    fn lookup(&self, name: &str) -> ExecResult<Value> {
+        let frame = self.frame();
+
+        if let Some(value) = frame.locals.borrow().get(name) {
+            return Ok(value.clone());
+        }
+
+        if let Some(value) = frame.globals.borrow().get(name) {
+            return Ok(value.clone());
+        }
+
+        if let Some(value) = self.builtins.borrow().get(name) {
+            return Ok(value.clone());
+        }
+
+        error("NameError", name)
+    }
+
+    fn scope_for(&self, name: &str) -> Namespace {
+        let frame = self.frame();
+
+        if frame.global_names.contains(name) {
+            frame.globals.clone()
+        } else {
+            frame.locals.clone()
+        }
+    }
+
+    fn store(&mut self, name: &str, value: Value) {
+        self.scope_for(name)
+            .borrow_mut()
+            .insert(name.to_string(), value);
+    }
+
+    fn assign(&mut self, target: &Target, value: Value) -> ExecResult {
+        match target {
+            Target::Name(name) => self.store(name, value),
+            Target::Tuple(targets) => {
+                let items = match &value {
+                    Value::Tuple(items) => items.to_vec(),
+                    Value::List(items) => items.borrow().clone(),
+                    Value::Str(s) => s.iter().map(|&byte| Value::bytes([byte])).collect(),
+                    _ => return error("TypeError", "unpack non-sequence"),
+                };
+
+                if items.len() != targets.len() {
+                    return error("ValueError", "unpack tuple of wrong size");
+                }
+
+                for (target, item) in targets.iter().zip(items) {
+                    self.assign(target, item)?;
+                }
+            }
+            Target::Attribute { object, name } => {
+                let object = self.eval(object)?;
+                self.set_attribute(&object, name, value)?;
+            }
+            Target::Index { object, index } => {
+                let object = self.eval(object)?;
+                let index = self.eval(index)?;
+                match &object {
+                    Value::List(items) => {
+                        let len = items.borrow().len();
+                        let Some(position) = sequence_index(&index, len)? else {
+                            return error("IndexError", "list assignment index out of range");
+                        };
+                        items.borrow_mut()[position] = value;
+                    }
+                    Value::Dict(dict) => dict.borrow_mut().insert(index, value)?,
+                    _ => return error("TypeError", "object doesn't support item assignment"),
+                }
+            }
+            Target::Slice {
+                object,
+                lower,
+                upper,
+            } => {
+                let object = self.eval(object)?;
+                let (lower, upper) = self.slice_bounds(&object, lower, upper)?;
+                let Value::List(items) = &object else {
+                    return error("TypeError", "object doesn't support slice assignment");
+                };
+                let Value::List(replacement) = &value else {
+                    return error("TypeError", "must assign list to slice");
+                };
+                // Copy first, since the list may be assigned into itself.
+                let replacement = replacement.borrow().clone();
+                items.borrow_mut().splice(lower..upper, replacement);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn delete(&mut self, target: &Target) -> ExecResult {
+        match target {
+            Target::Name(name) => {
+                if self.scope_for(name).borrow_mut().remove(name).is_none() {
+                    return error("NameError", name.clone());
+                }
+            }
+            Target::Tuple(targets) => {
+                for target in targets {
+                    self.delete(target)?;
+                }
+            }
+            Target::Attribute { object, name } => {
+                let dict = match self.eval(object)? {
+                    Value::Instance(instance) => instance.dict.clone(),
+                    Value::Class(class) => class.dict.clone(),
+                    Value::Module(module) => module.dict.clone(),
+                    _ => return error("TypeError", "attribute-less object (assign or del)"),
+                };
+
+                if dict.borrow_mut().remove(name).is_none() {
+                    return error("AttributeError", name.clone());
+                }
+            }
+            Target::Index { object, index } => {
+                let object = self.eval(object)?;
+                let index = self.eval(index)?;
+                match &object {
+                    Value::List(items) => {
+                        let len = items.borrow().len();
+                        let Some(position) = sequence_index(&index, len)? else {
+                            return error("IndexError", "list assignment index out of range");
+                        };
+                        items.borrow_mut().remove(position);
+                    }
+                    Value::Dict(dict) => {
+                        if dict.borrow_mut().remove(&index)?.is_none() {
+                            return key_error(&index);
+                        }
+                    }
+                    _ => return error("TypeError", "object doesn't support item deletion"),
+                }
+            }
+            Target::Slice {
+                object,
+                lower,
+                upper,
+            } => {
+                let object = self.eval(object)?;
+                let (lower, upper) = self.slice_bounds(&object, lower, upper)?;
+                let Value::List(items) = &object else {
+                    return error("TypeError", "object doesn't support slice deletion");
+                };
+                items.borrow_mut().drain(lower..upper);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn call(&mut self, callee: &Value, mut args: Vec<Value>) -> ExecResult<Value> {
+        match callee {
+            Value::Function(function) => self.call_function(function, args),
+            Value::Builtin(builtin) => (builtin.func)(self, args),
+            Value::Method(method) => {
+                args.insert(0, method.receiver.clone());
+                self.call(&method.function, args)
+            }
+            Value::Class(class) => {
+                let instance = Value::Instance(Rc::new(Instance {
+                    class: class.clone(),
+                    dict: new_namespace(),
+                }));
+
+                match class.lookup("__init__") {
+                    Some(init) => {
+                        args.insert(0, instance.clone());
+                        self.call(&init, args)?;
+                    }
+                    None if !args.is_empty() => {
+                        return error("TypeError", "this constructor takes no arguments");
+                    }
+                    None => {}
+                }
+
+                Ok(instance)
+            }
+            _ => error("TypeError", "call of non-function"),
+        }
+    }
+
+    fn eval_defaults(&mut self, params: &Parameters) -> ExecResult<Vec<Value>> {
+        params.defaults.iter().map(|expr| self.eval(expr)).collect()
+    }
+
+    fn call_function(&mut self, function: &Function, mut args: Vec<Value>) -> ExecResult<Value> {
+        let params = &function.params;
+        let max = params.positional.len();
+        let min = max - function.defaults.len();
+
+        if args.len() < min || (args.len() > max && params.varargs.is_none()) {
+            let (bound, count) = if args.len() < min {
+                let at_least = params.varargs.is_some() || min < max;
+                (if at_least { "at least" } else { "exactly" }, min)
+            } else {
+                (if min < max { "at most" } else { "exactly" }, max)
+            };
+            return error(
+                "TypeError",
+                format!(
+                    "{}() takes {bound} {count} argument{} ({} given)",
+                    function.name,
+                    if count == 1 { "" } else { "s" },
+                    args.len()
+                ),
+            );
+        }
+
+        if self.frames.len() > MAX_RECURSION_DEPTH {
+            return error("RuntimeError", "maximum recursion depth exceeded");
+        }
+
+        let rest = args.split_off(args.len().min(max));
+        args.extend(function.defaults[args.len() - min..].iter().cloned());
+
+        let frame = Frame::new(
+            new_namespace(),
+            function.globals.clone(),
+            &function.name,
+            function.source.clone(),
+        );
+
+        self.with_frame(frame, |interp| {
+            for (target, value) in params.positional.iter().zip(args) {
+                interp.assign(target, value)?;
+            }
+            if let Some(varargs) = &params.varargs {
+                interp.store(varargs, Value::tuple(rest));
+            }
+
+            match &function.body {
+                FunctionBody::Block(body) => match interp.exec_block(body) {
+                    Ok(()) => Ok(Value::None),
+                    Err(Unwind::Return(value)) => Ok(value),
+                    Err(Unwind::Break) => Err(Unwind::Error(syntax_error("'break' outside loop"))),
+                    Err(Unwind::Continue) => Err(Unwind::Error(syntax_error(
+                        "'continue' not properly in loop",
+                    ))),
+                    Err(err) => Err(err),
+                },
+                FunctionBody::Lambda(body) => interp.eval(body),
+            }
+        })
+    }
+
+    pub fn get_attribute(&mut self, object: &Value, name: &str) -> ExecResult<Value> {
+        let found = match object {
+            Value::Instance(instance) => {
+                let own = instance.dict.borrow().get(name).cloned();
+                match own {
+                    Some(value) => Some(value),
+                    None if name == "__class__" => Some(Value::Class(instance.class.clone())),
+                    None => instance.class.lookup(name).map(|value| match value {
+                        Value::Function(_) => Value::Method(Rc::new(Method {
+                            receiver: object.clone(),
+                            function: value,
+                        })),
+                        other => other,
+                    }),
+                }
+            }
+            Value::Class(class) => match name {
+                "__name__" => Some(Value::str(&class.name)),
+                _ => class.lookup(name),
+            },
+            Value::Module(module) => module.dict.borrow().get(name).cloned(),
+            Value::Function(function) if name == "__name__" => Some(Value::str(&function.name)),
+            _ => builtins::method(object, name).map(|builtin| {
+                Value::Method(Rc::new(Method {
+                    receiver: object.clone(),
+                    function: Value::Builtin(builtin),
+                }))
+            }),
+        };
+
+        match found {
+            Some(value) => Ok(value),
+            None => error("AttributeError", name),
+        }
+    }
+
+    pub fn set_attribute(&mut self, object: &Value, name: &str, value: Value) -> ExecResult {
+        let dict = match object {
+            Value::Instance(instance) => &instance.dict,
+            Value::Class(class) => &class.dict,
+            Value::Module(module) => &module.dict,
+            _ => return error("TypeError", "attribute-less object (assign or del)"),
+        };
+
+        dict.borrow_mut().insert(name.to_string(), value);
+        Ok(())
+    }
+
+    pub fn get_item(&mut self, object: &Value, index: &Value) -> ExecResult<Value> {
+        match object {
+            Value::Dict(dict) => {
+                let found = dict.borrow().get(index)?;
+                match found {
+                    Some(value) => Ok(value),
+                    None => key_error(index),
+                }
+            }
+            Value::Str(_) | Value::Tuple(_) | Value::List(_) => {
+                let Some(position) = sequence_index(index, sequence_len(object))? else {
+                    return error(
+                        "IndexError",
+                        format!("{} index out of range", object.type_name()),
+                    );
+                };
+
+                Ok(self.sequence_item(object, position)?.unwrap_or(Value::None))
+            }
+            _ => error("TypeError", "unsubscriptable object"),
+        }
+    }
+
+    fn sequence_item(&self, sequence: &Value, index: usize) -> ExecResult<Option<Value>> {
+        match sequence {
+            Value::Tuple(items) => Ok(items.get(index).cloned()),
+            Value::List(items) => Ok(items.borrow().get(index).cloned()),
+            Value::Str(s) => Ok(s.get(index).map(|&byte| Value::bytes([byte]))),
+            _ => error("TypeError", "loop over non-sequence"),
+        }
+    }
+
+    fn import(&mut self, name: &str) -> ExecResult<Value> {
+        if let Some(module) = self.modules.get(name) {
+            return Ok(module.clone());
+        }
+
+        if let Some(dict) = builtins::module(name) {
+            let module = Value::Module(Rc::new(Module {
+                name: name.to_string(),
+                dict,
+            }));
+            self.modules.insert(name.to_string(), module.clone());
+            return Ok(module);
+        }
+
+        let Some(path) = self
+            .search_path
+            .iter()
+            .map(|dir| dir.join(format!("{name}.py")))
+            .find(|path| path.is_file())
+        else {
+            return error("ImportError", format!("No module named {name}"));
+        };
+
+        let src = match read_to_string(&path) {
+            Ok(src) => src,
+            Err(err) => return error("IOError", err.to_string()),
+        };
+
+        let source = SourceFile::new(&path.to_string_lossy(), &src);
+        let mut lexer = Lexer::new(&src);
+        let tokens = match lexer.analyze() {
+            Ok(tokens) => tokens,
+            Err(errors) => {
+                return syntax_error_at(&source, &errors[0].to_string(), errors[0].span());
+            }
+        };
+        let mut parser = Parser::new(&tokens);
+        let statements = match parser.parse() {
+            Ok(statements) => statements,
+            Err(errors) => return syntax_error_at(&source, &errors[0].message, errors[0].span),
+        };
+
+        let dict = new_namespace();
+        dict.borrow_mut()
+            .insert("__name__".to_string(), Value::str(name));
+        let module = Value::Module(Rc::new(Module {
+            name: name.to_string(),
+            dict: dict.clone(),
+        }));
+        self.modules.insert(name.to_string(), module.clone());
+
+        let frame = Frame::new(dict.clone(), dict, "?", Rc::new(source));
+        if let Err(err) = self.with_frame(frame, |interp| interp.exec_block(&statements)) {
+            self.modules.remove(name);
+            return Err(err);
+        }
+
+        Ok(module)
+    }
+}
+
+pub fn sequence_len(sequence: &Value) -> usize {
+    match sequence {
+        Value::Str(s) => s.len(),
+        Value::Tuple(items) => items.len(),
+        Value::List(items) => items.borrow().len(),
+        Value::Dict(dict) => dict.borrow().len(),
+        _ => 0,
+    }
+}
+
+// Long indices act like ints, with those too big for an int saturating so
+// that they are out of range or clamp like any other large index.
+fn index_int(index: &Value) -> Option<i64> {
+    match index {
+        Value::Int(i) => Some(*i),
+        Value::Long(n) => {
+            Some(
+                n.to_i64()
+                    .unwrap_or(if n.is_negative() { i64::MIN } else { i64::MAX }),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Resolves a possibly negative sequence index, or `None` if it is out of range.
+fn sequence_index(index: &Value, len: usize) -> ExecResult<Option<usize>> {
+    let Some(i) = index_int(index) else {
+        return error("TypeError", "sequence index must be integer");
+    };
+    let position = if i < 0 { i + len as i64 } else { i };
+
+    if position < 0 || position as usize >= len {
+        Ok(None)
+    } else {
+        Ok(Some(position as usize))
+    }
+}
+
+fn slice(sequence: &Value, lower: usize, upper: usize) -> ExecResult<Value> {
+    match sequence {
+        Value::Str(s) => Ok(Value::bytes(&s[lower..upper])),
+        Value::Tuple(items) => Ok(Value::tuple(items[lower..upper].to_vec())),
+        Value::List(items) => Ok(Value::list(items.borrow()[lower..upper].to_vec())),
+        _ => error("TypeError", "only sequences can be sliced"),
+    }
+}
+
+fn exception_matches(exc: &Exception, matcher: &Value) -> bool {
+    match (matcher, &exc.kind) {
+        (Value::Str(expected), Value::Str(kind)) => expected == kind,
+        (Value::Class(expected), Value::Class(kind)) => kind.is_subclass(expected),
+        (Value::Tuple(items), _) => items.iter().any(|item| exception_matches(exc, item)),
+        _ => false,
+    }
+}
+
+fn at_statement(unwind: Unwind, stmt: &Stmt) -> Unwind {
+    match unwind {
+        Unwind::Error(mut exc) => {
+            exc.span.get_or_insert(stmt.span);
+            Unwind::Error(exc)
+        }
+        other => other,
+    }
+}
+
+fn syntax_error_at<T>(source: &SourceFile, message: &str, span: Span) -> ExecResult<T> {
+    let (line, _) = source.location(span.0);
+    error(
+        "SyntaxError",
+        format!("{message} ({}, line {line})", source.name),
+    )
+}
+
+fn syntax_error(message: &str) -> Exception {
+    Exception::new(Value::str("SyntaxError"), Value::str(message))
+}
@@ -0,0 +1,643 @@
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use crate::{
+    diagnostics::source::SourceFile,
+    frontend::{lexer::Lexer, parser::Parser},
+};
+
+use super::{exception::Exception, interpreter::Interpreter};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn execute_bytes(src: &str) -> (Vec<u8>, Result<(), Exception>) {
+    let src = src.to_string();
+    let mut lexer = Lexer::new(&src);
+    let tokens = lexer.analyze().unwrap();
+
+    let mut parser = Parser::new(&tokens);
+    let statements = parser.parse().unwrap();
+
+    let buffer = SharedBuffer::default();
+    let mut interpreter = Interpreter::with_output(Box::new(buffer.clone()));
+    let result = interpreter.run(SourceFile::new("test.py", &src), &statements);
+
+    let output = buffer.0.borrow().clone();
+    (output, result)
+}
+
+fn execute(src: &str) -> (String, Result<(), Exception>) {
+    let (output, result) = execute_bytes(src);
+    (String::from_utf8(output).unwrap(), result)
+}
+
+fn run(src: &str) -> String {
+    let (output, result) = execute(src);
+    if let Err(err) = result {
+        panic!("Unexpected runtime error: {err}\nOutput so far:\n{output}");
+    }
+    output
+}
+
+fn run_error(src: &str) -> Exception {
+    match execute(src).1 {
+        Err(err) => err,
+        Ok(()) => panic!("Expected a runtime error"),
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    let output = run(
+        "print 1 + 2 * 3\nprint 7 / 2\nprint -7 / 2\nprint 7 % -3\nprint 2 ** 10\nprint 1.5 * 2\n",
+    );
+    assert_eq!(output, "7\n3\n-4\n-2\n1024\n3.0\n");
+}
+
+#[test]
+fn test_numeric_literals() {
+    let src = "print (0x1F, 017, 1e3, .5)\nprint (123L, 99999999999999999999)\nprint (type(1L), 1L == 1, 0L < 1.5)\nd = {1: 'int'}\nprint d[1L]\n";
+    assert_eq!(
+        run(src),
+        "(31, 15, 1000.0, 0.5)\n(123L, 99999999999999999999L)\n(<type 'long int'>, 1, 1)\nint\n"
+    );
+}
+
+#[test]
+fn test_int_overflow_promotes_to_long() {
+    let src = "print 2 ** 100\nprint 9223372036854775807 + 1\nprint -9223372036854775807 - 2\nprint type(3037000500 * 3037000500)\nprint -(-9223372036854775807 - 1)\n";
+    assert_eq!(
+        run(src),
+        "1267650600228229401496703205376L\n9223372036854775808L\n-9223372036854775809L\n<type 'long int'>\n9223372036854775808L\n"
+    );
+}
+
+#[test]
+fn test_long_arithmetic() {
+    let src = "n = 1L\nfor i in range(1, 31):\n    n = n * i\nprint n\nprint (n / 7L ** 5, n % 1000007, -n / 10 ** 20)\nprint (divmod(-7L, 2), 7L & -4, -1L ^ 5, ~5L, 5L | 8)\nprint (1L + 0.5, 1L == 1.0, 2L ** 64 > 2 ** 63)\n";
+    assert_eq!(
+        run(src),
+        "265252859812191058636308480000000L\n(15782284751126974393782857142L, 790627L, -2652528598122L)\n((-4L, 1L), 4L, -6L, -6L, 13L)\n(1.5, 1, 1)\n"
+    );
+}
+
+#[test]
+fn test_long_conversions() {
+    let src = "print (long(5), long(-2.7), long(' 123456789012345678901234 '))\nprint (int(7L), float(2L ** 70), hex(255L), oct(8L), abs(-3L))\nprint '%d %x %o' % (2L ** 64, 2 ** 65, -8L)\n";
+    assert_eq!(
+        run(src),
+        "(5L, -2L, 123456789012345678901234L)\n(7, 1.18059162072e+21, '0xffL', '010L', 3L)\n18446744073709551616 20000000000000000 -10\n"
+    );
+}
+
+#[test]
+fn test_long_errors() {
+    let err = run_error("print 1L / 0\n");
+    assert_eq!(err.kind_name(), "ZeroDivisionError");
+    assert_eq!(err.message(), "long division or modulo");
+
+    let err = run_error("print int(2L ** 64)\n");
+    assert_eq!(err.kind_name(), "OverflowError");
+
+    let err = run_error("print long('12x')\n");
+    assert_eq!(err.kind_name(), "ValueError");
+}
+
+#[test]
+fn test_comparisons_and_logic() {
+    let output = run("print 1 < 2\nprint 2 == 3\nprint 0 or 'x'\nprint 1 and 0\nprint not []\n");
+    assert_eq!(output, "1\n0\nx\n0\n1\n");
+}
+
+#[test]
+fn test_mixed_numeric_comparisons_agree_with_float_comparisons() {
+    let src = "print (0.0 == -0.0, 0 == -0.0, -0.0 == 0, 0L == -0.0, -0.0 < 0, -0.0 >= 0L)\n";
+    assert_eq!(run(src), "(1, 1, 1, 1, 0, 1)\n");
+}
+
+#[test]
+fn test_logical_lines_span_brackets_and_backslashes() {
+    let src = "def add(a,\n        b):\n    return a + \\\n        b\nd = {\n  'x': add(1,\n            2)\n}\nprint d['x']\n";
+    assert_eq!(run(src), "3\n");
+}
+
+#[test]
+fn test_blank_lines_inside_blocks() {
+    let src = "def f(x):\n    y = x + 1\n\n# comment\n\n    return y * 2\nprint f(1)";
+    assert_eq!(run(src), "4\n");
+}
+
+#[test]
+fn test_membership_operators() {
+    let src = "d = {'k': 1}\nprint ('b' in 'abc', 'z' not in 'abc', 2 in [1, 2], 3 not in (1, 2))\nprint ('k' in d, 'v' in d, 1.0 in [1])\nx = [1]\nprint (x is x, x is not [1], None is not None)\n";
+    assert_eq!(run(src), "(1, 1, 1, 1)\n(1, 0, 1)\n(1, 1, 0)\n");
+}
+
+#[test]
+fn test_membership_errors() {
+    let err = run_error("print 'ab' in 'abc'\n");
+    assert_eq!(err.kind_name(), "TypeError");
+    assert_eq!(err.message(), "string member test needs char left operand");
+
+    let err = run_error("print 1 in 2\n");
+    assert_eq!(err.kind_name(), "TypeError");
+    assert_eq!(
+        err.message(),
+        "'in' or 'not in' needs sequence right argument"
+    );
+}
+
+#[test]
+fn test_chained_comparisons() {
+    let src = "def f(x):\n    print ('f', x)\n    return x\nprint (1 < 2 < 3, 3 > 2 > 1, 1 < 3 < 2, 1 == 1 <> 2, 2 <> 2)\nprint 1 < f(2) <= 2\nprint 2 < 1 < f(3)\n";
+    assert_eq!(run(src), "(1, 1, 0, 1, 0)\n('f', 2)\n1\n0\n");
+}
+
+#[test]
+fn test_shift_operators() {
+    let src = "print (1 << 4, 256 >> 2, -17 >> 2, 5 >> 100, 1 << 1 + 1)\nprint (1 << 64, 3L << 2, -(2L ** 70) >> 68, 1 << 63 >> 63)\n";
+    assert_eq!(
+        run(src),
+        "(16, 64, -5, 0, 4)\n(18446744073709551616L, 12L, -4L, 1L)\n"
+    );
+
+    let err = run_error("print 1 >> -1\n");
+    assert_eq!(err.kind_name(), "ValueError");
+    assert_eq!(err.message(), "negative shift count");
+}
+
+#[test]
+fn test_int_results_that_fit_stay_ints() {
+    let src = "print (0 << 64, 0 << 2 ** 40, -1 << 63, 5 >> 2 ** 40)\n";
+    assert_eq!(run(src), "(0, 0, -9223372036854775808, 0)\n");
+}
+
+#[test]
+fn test_function_recursion() {
+    let src = "def fact(n):\n    if n <= 1:\n        return 1\n    return n * fact(n - 1)\nprint fact(10)\n";
+    assert_eq!(run(src), "3628800\n");
+}
+
+#[test]
+fn test_function_without_return_gives_none() {
+    let src = "def f():\n    pass\nprint f()\n";
+    assert_eq!(run(src), "None\n");
+}
+
+#[test]
+fn test_while_with_break_and_continue() {
+    let src = "i = 0\ntotal = 0\nwhile i < 10:\n    i = i + 1\n    if i % 2:\n        continue\n    if i > 8:\n        break\n    total = total + i\nprint total\n";
+    assert_eq!(run(src), "20\n");
+}
+
+#[test]
+fn test_for_over_sequences() {
+    let src = "s = 0\nfor x in [1, 2, 3]:\n    s = s + x\nfor c in 'ab':\n    print c\nfor a, b in [(1, 2), (3, 4)]:\n    s = s + a * b\nprint s\n";
+    assert_eq!(run(src), "a\nb\n20\n");
+}
+
+#[test]
+fn test_classes_and_methods() {
+    let src = "class Counter:\n    def __init__(self, start):\n        self.count = start\n    def bump(self):\n        self.count = self.count + 1\n        return self.count\n\nclass Loud(Counter):\n    def bump(self):\n        return Counter.bump(self) * 100\n\nc = Loud(4)\nprint c.bump()\nprint c.count\n";
+    assert_eq!(run(src), "500\n5\n");
+}
+
+#[test]
+fn test_list_aliasing() {
+    let src = "a = [1]\nb = a\nb.append(2)\nprint a\n";
+    assert_eq!(run(src), "[1, 2]\n");
+}
+
+#[test]
+fn test_global_statement() {
+    let src = "n = 1\ndef bump():\n    global n\n    n = n + 1\ndef shadow():\n    n = 100\nbump()\nshadow()\nprint n\n";
+    assert_eq!(run(src), "2\n");
+}
+
+#[test]
+fn test_lambda() {
+    assert_eq!(run("add = lambda a, b: a + b\nprint add(2, 3)\n"), "5\n");
+}
+
+#[test]
+fn test_try_except_catches_matching_exception() {
+    let src = "try:\n    x = {}['missing']\nexcept IndexError:\n    print 'index'\nexcept KeyError:\n    print 'key'\n";
+    assert_eq!(run(src), "key\n");
+}
+
+#[test]
+fn test_raise_string_exception() {
+    let src = "MyError = 'MyError'\ntry:\n    raise MyError\n    print 'unreachable'\nexcept MyError:\n    print 'caught'\n";
+    assert_eq!(run(src), "caught\n");
+}
+
+#[test]
+fn test_uncaught_exception() {
+    let err = run_error("print undefined_name\n");
+    assert_eq!(err.kind_name(), "NameError");
+    assert_eq!(err.message(), "undefined_name");
+}
+
+#[test]
+fn test_zero_division() {
+    let err = run_error("x = 1 / 0\n");
+    assert_eq!(err.kind_name(), "ZeroDivisionError");
+}
+
+#[test]
+fn test_del_statement() {
+    let err = run_error("x = 1\ndel x\nprint x\n");
+    assert_eq!(err.kind_name(), "NameError");
+}
+
+#[test]
+fn test_string_formatting() {
+    let src = "print '%s has %d items (%.2f%%)' % ('cart', 3, 12.5)\nprint '%5s|%-4d|%x' % ('ab', 7, 255)\n";
+    assert_eq!(run(src), "cart has 3 items (12.50%)\n   ab|7   |ff\n");
+}
+
+#[test]
+fn test_repr_of_values() {
+    let src = "print [1, 'a', (2,), None, 1.0]\nprint {'k': [0.5]}\n";
+    assert_eq!(run(src), "[1, 'a', (2,), None, 1.0]\n{'k': [0.5]}\n");
+}
+
+#[test]
+fn test_builtins() {
+    let src = "print (len('abc'), range(3), max(3, 9, 2), min([4, 1]))\n";
+    assert_eq!(run(src), "(3, [0, 1, 2], 9, 1)\n");
+}
+
+#[test]
+fn test_range_near_integer_bounds() {
+    let src = "print range(0, 9223372036854775807, 4611686018427387904)\nlow = -9223372036854775807 - 1\nprint range(low + 3, low, -2)\nprint len(range(9223372036854775806, 9223372036854775807))\n";
+    assert_eq!(
+        run(src),
+        "[0, 4611686018427387904]\n[-9223372036854775805, -9223372036854775807]\n1\n"
+    );
+}
+
+#[test]
+fn test_type_objects() {
+    let src = "print (type(1), type('s'), type([]))\nprint type(1) is type(2)\nprint type(1) is type(1.0)\nprint type(type(1))\n";
+    assert_eq!(
+        run(src),
+        "(<type 'int'>, <type 'string'>, <type 'list'>)\n1\n0\n<type 'type'>\n"
+    );
+}
+
+#[test]
+fn test_types_module() {
+    let src = "import types\nprint type({}) is types.DictType\nprint type(len) is types.BuiltinFunctionType\n";
+    assert_eq!(run(src), "1\n1\n");
+}
+
+#[test]
+fn test_identity_of_mutable_objects() {
+    let src = "a = [1]\nb = a\nc = [1]\nprint a is b\nprint a is c\nprint a == c\nprint id(a) == id(b)\nprint id(a) == id(c)\n";
+    assert_eq!(run(src), "1\n0\n1\n1\n0\n");
+}
+
+#[test]
+fn test_identity_of_immediates_uses_whole_value() {
+    let src = "print (0 is 2305843009213693952, 5 is 5, id(0) == id(2305843009213693952))\nprint (id(-1) == id(-1), id(9223372036854775807) > 0, None is None)\n";
+    assert_eq!(run(src), "(0, 1, 0)\n(1, 1, 1)\n");
+}
+
+#[test]
+fn test_dict_aliasing_and_methods() {
+    let src = "d = {'a': 1}\ne = d\ne.update({'b': 2})\nprint (d.has_key('b'), len(d))\nc = d.copy()\nc.clear()\nprint (len(d), d.get('z', 0))\n";
+    assert_eq!(run(src), "(1, 2)\n(2, 0)\n");
+}
+
+#[test]
+fn test_dict_keys_hash_by_value() {
+    let src = "d = {1: 'int', (1, 'x'): 'tuple'}\nprint d[1.0]\nprint d[(1, 'x')]\nd.update({1.0: 'again'})\nprint d\n";
+    assert_eq!(run(src), "int\ntuple\n{1: 'again', (1, 'x'): 'tuple'}\n");
+}
+
+#[test]
+fn test_unhashable_dict_key() {
+    let err = run_error("d = {[1]: 2}\n");
+    assert_eq!(err.kind_name(), "TypeError");
+    assert_eq!(err.message(), "unhashable type");
+}
+
+#[test]
+fn test_raise_string_exception_with_value() {
+    let src = "def check(n):\n    if n < 0:\n        raise 'Negative', n\n    return n\ntry:\n    check(-3)\nexcept 'Negative':\n    print 'negative'\n";
+    assert_eq!(run(src), "negative\n");
+
+    let err = run_error("raise 'Custom', (1, 2)\n");
+    assert_eq!(err.to_string(), "Custom: (1, 2)");
+}
+
+#[test]
+fn test_class_exceptions_match_subclasses() {
+    let src = "class Base:\n    pass\nclass Derived(Base):\n    def __init__(self, code):\n        self.code = code\ntry:\n    raise Derived, 7\nexcept Base:\n    print 'base'\ntry:\n    raise Derived(8)\nexcept (KeyError, Derived):\n    print 'derived'\n";
+    assert_eq!(run(src), "base\nderived\n");
+
+    let err = run_error("class Oops:\n    pass\nraise Oops\n");
+    assert_eq!(err.kind_name(), "Oops");
+}
+
+#[test]
+fn test_unmatched_exception_propagates() {
+    let src = "try:\n    raise 'Inner'\nexcept 'Other':\n    print 'wrong'\n";
+    assert_eq!(run_error(src).kind_name(), "Inner");
+}
+
+#[test]
+fn test_bare_raise_reraises_handled_exception() {
+    let src = "try:\n    try:\n        x = [][1]\n    except IndexError:\n        print 'inner'\n        raise\nexcept IndexError:\n    print 'outer'\n";
+    assert_eq!(run(src), "inner\nouter\n");
+}
+
+#[test]
+fn test_invalid_raise() {
+    let err = run_error("raise 42\n");
+    assert_eq!(err.kind_name(), "TypeError");
+    assert_eq!(
+        err.message(),
+        "exceptions must be strings, classes, or instances"
+    );
+}
+
+#[test]
+fn test_traceback_through_function_frames() {
+    let src = "def inner(x):\n    return x / 0\n\ndef outer():\n    y = 1\n    return inner(y)\n\nouter()\n";
+    let err = run_error(src);
+    assert_eq!(
+        err.traceback(),
+        b"Traceback (innermost last):\n  File \"test.py\", line 8, in ?\n    outer()\n  File \"test.py\", line 6, in outer\n    return inner(y)\n  File \"test.py\", line 2, in inner\n    return x / 0\nZeroDivisionError: integer division or modulo"
+    );
+}
+
+#[test]
+fn test_traceback_points_at_nested_statement() {
+    let src = "for i in range(3):\n    if i == 2:\n        undefined\n";
+    let err = run_error(src);
+    assert_eq!(
+        err.traceback(),
+        b"Traceback (innermost last):\n  File \"test.py\", line 3, in ?\n    undefined\nNameError: undefined"
+    );
+}
+
+#[test]
+fn test_unicode_source_text() {
+    let src = "# grüße\ns = 'naïve café'\nprint s\nprint missing\n";
+    let (output, result) = execute(src);
+
+    assert_eq!(output, "naïve café\n");
+    assert_eq!(
+        result.unwrap_err().traceback(),
+        b"Traceback (innermost last):\n  File \"test.py\", line 4, in ?\n    print missing\nNameError: missing"
+    );
+}
+
+#[test]
+fn test_traceback_keeps_exception_value_bytes() {
+    let err = run_error("raise 'Erreur', 'café'\n");
+    assert_eq!(
+        err.traceback(),
+        "Traceback (innermost last):\n  File \"test.py\", line 1, in ?\n    raise 'Erreur', 'café'\nErreur: café".as_bytes()
+    );
+
+    let err = run_error("raise 'E', '\\xe9'\n");
+    assert!(err.traceback().ends_with(b"\nE: \xe9"));
+}
+
+#[test]
+fn test_escaped_bytes_round_trip_through_repr() {
+    let src = "s = '\\0\\x7f\\t\\q' '\\101'\nprint len(s)\nprint repr(s)\n";
+    assert_eq!(run(src), "6\n'\\000\\177\\011\\\\qA'\n");
+}
+
+#[test]
+fn test_slicing_sequences() {
+    let src = "s = 'hello'\nprint s[1:3]\nprint s[:-1]\nprint [1, 2, 3][-2:]\nprint (1, 2, 3)[:]\nprint [1, 2][5:9]\nprint s[3:1]\n";
+    assert_eq!(run(src), "el\nhell\n[2, 3]\n(1, 2, 3)\n[]\n\n");
+}
+
+#[test]
+fn test_item_and_slice_assignment() {
+    let src = "a = [1, 2, 3]\na[0] = 9\na[-1] = 8\nprint a\na[1:2] = [7, 7]\nprint a\na[:0] = a\nprint a\nd = {}\nd['k'] = 1\nprint d\n";
+    assert_eq!(
+        run(src),
+        "[9, 2, 8]\n[9, 7, 7, 8]\n[9, 7, 7, 8, 9, 7, 7, 8]\n{'k': 1}\n"
+    );
+}
+
+#[test]
+fn test_item_and_slice_deletion() {
+    let src = "a = [1, 2, 3, 4]\ndel a[0]\nprint a\ndel a[1:]\nprint a\nd = {'a': 1, 'b': 2, 'c': 3}\ndel d['a'], d['c']\nprint d\nprint d['b']\n";
+    assert_eq!(run(src), "[2, 3, 4]\n[2]\n{'b': 2}\n2\n");
+}
+
+#[test]
+fn test_invalid_subscript_assignment() {
+    let err = run_error("a = [1]\na[1] = 2\n");
+    assert_eq!(err.kind_name(), "IndexError");
+    assert_eq!(err.message(), "list assignment index out of range");
+
+    let err = run_error("t = (1, 2)\nt[0] = 2\n");
+    assert_eq!(err.kind_name(), "TypeError");
+    assert_eq!(err.message(), "object doesn't support item assignment");
+
+    let err = run_error("a = [1]\na[0:1] = (2,)\n");
+    assert_eq!(err.kind_name(), "TypeError");
+    assert_eq!(err.message(), "must assign list to slice");
+
+    let err = run_error("d = {}\ndel d['x']\n");
+    assert_eq!(err.kind_name(), "KeyError");
+    assert_eq!(err.message(), "x");
+
+    let err = run_error("print 'abc'['a':]\n");
+    assert_eq!(err.kind_name(), "TypeError");
+    assert_eq!(err.message(), "slice index must be int");
+}
+
+#[test]
+fn test_except_binds_exception_value() {
+    let src = "try:\n    raise 'oops', 42\nexcept 'oops', value:\n    print value\nclass E:\n    pass\ntry:\n    raise E\nexcept (KeyError, E), exc:\n    print exc.__class__ is E\n";
+    assert_eq!(run(src), "42\n1\n");
+}
+
+#[test]
+fn test_finally_runs_on_every_exit() {
+    let src = "def f():\n    try:\n        return 1\n    finally:\n        print 'return'\nprint f()\nfor i in (1, 2):\n    try:\n        break\n    finally:\n        print 'break'\ntry:\n    try:\n        raise 'inner'\n    finally:\n        print 'raise'\nexcept 'inner':\n    print 'caught'\n";
+    assert_eq!(run(src), "return\n1\nbreak\nraise\ncaught\n");
+}
+
+#[test]
+fn test_finally_reraises_after_running() {
+    let (output, result) = execute("try:\n    raise 'boom'\nfinally:\n    print 'cleanup'\n");
+    assert_eq!(output, "cleanup\n");
+    assert_eq!(result.unwrap_err().kind_name(), "boom");
+}
+
+#[test]
+fn test_loop_else_runs_without_break() {
+    let src = "def find(items, wanted):\n    for item in items:\n        if item == wanted:\n            print ('found', item)\n            break\n    else:\n        print ('missing', wanted)\nfind([1, 2], 2)\nfind([1, 2], 3)\nn = 3\nwhile n:\n    n = n - 1\n    if n == 5:\n        break\nelse:\n    print ('done', n)\nwhile 1:\n    break\nelse:\n    print 'unreachable'\n";
+    assert_eq!(run(src), "('found', 2)\n('missing', 3)\n('done', 0)\n");
+}
+
+#[test]
+fn test_inline_suites_and_semicolons() {
+    let src = "def sign(x):\n    if x < 0: return -1\n    elif x == 0: return 0\n    else: return 1\na = 0; b = 0\nfor i in (-5, 0, 5): a = a + sign(i); b = b + 1\nprint (a, b)\n";
+    assert_eq!(run(src), "(0, 3)\n");
+}
+
+#[test]
+fn test_print_separates_items_with_spaces() {
+    let src =
+        "print 1, 'a', (2, 3)\nprint\nfor i in range(3): print i,\nprint\nprint 'x',; print 'y'\n";
+    assert_eq!(run(src), "1 a (2, 3)\n\n0 1 2\nx y\n");
+}
+
+#[test]
+fn test_print_softspace_after_whitespace() {
+    let src = "print 'line\\n',\nprint 'next'\nprint 'tab\\t',\nprint 'z'\n";
+    assert_eq!(run(src), "line\nnext\ntab\tz\n");
+}
+
+#[test]
+fn test_trailing_comma_line_is_ended() {
+    let (output, result) = execute("print 'partial',\nraise 'stop'\n");
+    assert_eq!(output, "partial\n");
+    assert!(result.is_err());
+
+    assert_eq!(run("print 'end',\n"), "end\n");
+}
+
+#[test]
+fn test_default_parameters_evaluated_at_definition() {
+    let src = "n = 1\ndef f(a, b=n, c=n + 1): return (a, b, c)\nn = 10\nprint f(0)\nprint f(0, 5)\nprint f(0, 5, 6)\nadd = lambda x, y=3: x + y\nprint add(1)\n";
+    assert_eq!(run(src), "(0, 1, 2)\n(0, 5, 2)\n(0, 5, 6)\n4\n");
+}
+
+#[test]
+fn test_varargs_and_tuple_parameters() {
+    let src = "def f(a, *rest): return (a, rest)\nprint f(1)\nprint f(1, 2, 3)\ndef g((a, b), c): return a + b + c\nprint g((1, 2), 3)\nprint (lambda *args: len(args))(4, 5)\n";
+    assert_eq!(run(src), "(1, ())\n(1, (2, 3))\n6\n2\n");
+}
+
+#[test]
+fn test_arity_errors() {
+    let cases = [
+        (
+            "def f(a, b): pass\nf(1)\n",
+            "f() takes exactly 2 arguments (1 given)",
+        ),
+        (
+            "def f(a, b=1): pass\nf()\n",
+            "f() takes at least 1 argument (0 given)",
+        ),
+        (
+            "def f(a, b=1): pass\nf(1, 2, 3)\n",
+            "f() takes at most 2 arguments (3 given)",
+        ),
+        (
+            "def f(a, *b): pass\nf()\n",
+            "f() takes at least 1 argument (0 given)",
+        ),
+        (
+            "def f(): pass\nf(1)\n",
+            "f() takes exactly 0 arguments (1 given)",
+        ),
+    ];
+
+    for (src, message) in cases {
+        let err = run_error(src);
+        assert_eq!(err.kind_name(), "TypeError");
+        assert_eq!(err.message(), message);
+    }
+}
+
+#[test]
+fn test_strings_are_written_as_bytes() {
+    let src = "print '\\xff\\x80',\nprint len('\\xff'), len('é')\nprint repr('\\xe9é')\n";
+    let (output, result) = execute_bytes(src);
+    result.unwrap();
+
+    assert_eq!(output, b"\xff\x80 1 2\n'\\351\\303\\251'\n");
+}
+
+#[test]
+fn test_strings_are_indexed_by_byte() {
+    let src = "s = 'a\\xffé'\nfor c in s: print ord(c),\nprint\nprint s[1], s[-2:], '%s|%-3s|' % (s[0], s[1]), '\\xff' in s\n";
+    let (output, result) = execute_bytes(src);
+    result.unwrap();
+
+    assert_eq!(output, b"97 255 195 169\n\xff \xc3\xa9 a|\xff  | 1\n");
+}
+
+#[test]
+fn test_slice_bounds_that_shrink_the_list() {
+    let src = "def f():\n    del a[0:4]\n    return 4\n\na = [1, 2, 3, 4, 5]\nprint a[0:f()]\na = [1, 2, 3, 4, 5]\na[1:f()] = [9]\nprint a\na = [1, 2, 3, 4, 5]\ndel a[f():]\nprint a\n";
+    assert_eq!(run(src), "[5]\n[5, 9]\n[5]\n");
+}
+
+#[test]
+fn test_long_indices_and_slice_bounds() {
+    let src = "s = 'hello'\nprint (s[1L], s[-1L], s[1L:3L], s[-99999999999999999999:99999999999999999999])\na = [1, 2]\na[0L] = 5\ndel a[-1L]\nprint a\n";
+    assert_eq!(run(src), "('e', 'o', 'el', 'hello')\n[5]\n");
+
+    let err = run_error("print [1][99999999999999999999]\n");
+    assert_eq!(err.kind_name(), "IndexError");
+    assert_eq!(err.message(), "list index out of range");
+}
+
+#[test]
+fn test_sort_with_comparison_function() {
+    let src = "l = [3, 1, 2]\nl.sort()\nprint l\ndef desc(a, b): return b - a\nl.sort(desc)\nprint l\npairs = [(1, 'b'), (0, 'x'), (1, 'a')]\npairs.sort(lambda a, b: a[0] - b[0])\nprint pairs\n";
+    assert_eq!(
+        run(src),
+        "[1, 2, 3]\n[3, 2, 1]\n[(0, 'x'), (1, 'b'), (1, 'a')]\n"
+    );
+}
+
+#[test]
+fn test_sort_tolerates_inconsistent_comparison() {
+    let src = "seed = 1\ndef rnd(a, b):\n    global seed\n    seed = (seed * 1103515245 + 12345) % 2147483648\n    return seed % 3 - 1\nl = range(500)\nl.sort(rnd)\nprint len(l)\nl.sort()\nprint l == range(500)\n";
+    assert_eq!(run(src), "500\n1\n");
+
+    let err = run_error("def fail(a, b): raise 'stop'\nl = [2, 1]\nl.sort(fail)\n");
+    assert_eq!(err.kind_name(), "stop");
+}
+
+#[test]
+fn test_huge_repetition_raises_memory_error() {
+    for src in [
+        "x = [1] * 4000000000000000000\n",
+        "x = 'ab' * 4000000000000000000\n",
+        "x = (1, 2) * 9223372036854775807\n",
+    ] {
+        assert_eq!(run_error(src).kind_name(), "MemoryError");
+    }
+
+    assert_eq!(
+        run("print [] * 4000000000000000000, 'ab' * 2\n"),
+        "[] abab\n"
+    );
+}
+
+#[test]
+fn test_dict_deletion_keeps_order_and_lookups() {
+    let src = "d = {}\nfor i in range(1000): d[i] = i * i\nfor i in range(0, 1000, 2): del d[i]\nfor i in range(3, 990, 2): del d[i]\nprint d\nprint (d[1], d.has_key(2), len(d))\nd[2] = 'back'\nd[1.0] = 'one'\nprint d\n";
+    assert_eq!(
+        run(src),
+        "{1: 1, 991: 982081, 993: 986049, 995: 990025, 997: 994009, 999: 998001}\n(1, 0, 6)\n{1: 'one', 991: 982081, 993: 986049, 995: 990025, 997: 994009, 999: 998001, 2: 'back'}\n"
+    );
+}
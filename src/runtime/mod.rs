@@ -0,0 +1,10 @@
+pub mod builtins;
+pub mod dict;
+pub mod exception;
+pub mod format;
+pub mod interpreter;
+pub mod ops;
+pub mod value;
+
+#[cfg(test)]
+mod interpreter_tests;
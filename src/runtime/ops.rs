@@ -0,0 +1,291 @@
+use std::rc::Rc;
+
+use crate::core::{ast::CmpOp, bigint::BigInt, token::TokenType};
+
+use super::{
+    format::{FormatError, format_percent},
+    interpreter::{ExecResult, error, key_error},
+    value::{Value, compare},
+};
+
+pub fn binary(op: TokenType, left: &Value, right: &Value) -> ExecResult<Value> {
+    match (left, right) {
+        (Value::Int(a), Value::Int(b)) => int_binary(op, *a, *b),
+        (Value::Int(_) | Value::Long(_), Value::Int(_) | Value::Long(_)) => {
+            long_binary(op, &to_long(left), &to_long(right))
+        }
+        (
+            Value::Int(_) | Value::Long(_) | Value::Float(_),
+            Value::Int(_) | Value::Long(_) | Value::Float(_),
+        ) => float_binary(op, to_float(left), to_float(right)),
+        (Value::Str(a), Value::Str(b)) if op == TokenType::Plus => {
+            Ok(Value::bytes([a.as_ref(), b.as_ref()].concat()))
+        }
+        (Value::Str(template), args) if op == TokenType::Modulo => {
+            match format_percent(template, args) {
+                Ok(text) => Ok(Value::bytes(text)),
+                Err(FormatError::TypeError(message)) => error("TypeError", message),
+                Err(FormatError::KeyError(key)) => key_error(&key),
+            }
+        }
+        (Value::Tuple(a), Value::Tuple(b)) if op == TokenType::Plus => {
+            Ok(Value::tuple(a.iter().chain(b.iter()).cloned().collect()))
+        }
+        (Value::List(a), Value::List(b)) if op == TokenType::Plus => {
+            let items = a
+                .borrow()
+                .iter()
+                .chain(b.borrow().iter())
+                .cloned()
+                .collect();
+            Ok(Value::list(items))
+        }
+        (sequence, Value::Int(count)) | (Value::Int(count), sequence) if op == TokenType::Star => {
+            repeat(sequence, *count)
+        }
+        _ => error("TypeError", format!("unsupported operand type(s) for {op}")),
+    }
+}
+
+pub fn compare_op(op: CmpOp, left: &Value, right: &Value) -> ExecResult<bool> {
+    Ok(match op {
+        CmpOp::Equal => left == right,
+        CmpOp::NotEqual => left != right,
+        CmpOp::Less => compare(left, right).is_lt(),
+        CmpOp::LessEqual => compare(left, right).is_le(),
+        CmpOp::Greater => compare(left, right).is_gt(),
+        CmpOp::GreaterEqual => compare(left, right).is_ge(),
+        CmpOp::Is => left.is(right),
+        CmpOp::IsNot => !left.is(right),
+        CmpOp::In => contains(right, left)?,
+        CmpOp::NotIn => !contains(right, left)?,
+    })
+}
+
+pub fn contains(container: &Value, item: &Value) -> ExecResult<bool> {
+    match container {
+        Value::Str(s) => match item {
+            Value::Str(ch) if ch.len() == 1 => Ok(s.contains(&ch[0])),
+            _ => error("TypeError", "string member test needs char left operand"),
+        },
+        Value::Tuple(items) => Ok(items.contains(item)),
+        Value::List(items) => Ok(items.borrow().contains(item)),
+        Value::Dict(entries) => entries.borrow().contains_key(item),
+        _ => error(
+            "TypeError",
+            "'in' or 'not in' needs sequence right argument",
+        ),
+    }
+}
+
+fn repeat(sequence: &Value, count: i64) -> ExecResult<Value> {
+    let count = count.max(0) as usize;
+
+    match sequence {
+        Value::Str(s) => Ok(Value::bytes(repeat_items(s, count)?)),
+        Value::Tuple(items) => Ok(Value::tuple(repeat_items(items, count)?)),
+        Value::List(items) => Ok(Value::list(repeat_items(&items.borrow(), count)?)),
+        _ => error("TypeError", "unsupported operand type(s) for *"),
+    }
+}
+
+// The result is reserved up front, so a count too large to allocate raises
+// MemoryError rather than aborting the process.
+fn repeat_items<T: Clone>(items: &[T], count: usize) -> ExecResult<Vec<T>> {
+    let mut repeated = vec![];
+    let len = items.len().checked_mul(count);
+    if len.is_none_or(|len| repeated.try_reserve_exact(len).is_err()) {
+        return error("MemoryError", "");
+    }
+
+    repeated.extend(items.iter().cycle().take(items.len() * count).cloned());
+    Ok(repeated)
+}
+
+pub fn to_float(value: &Value) -> f64 {
+    match value {
+        Value::Int(i) => *i as f64,
+        Value::Long(n) => n.to_f64(),
+        Value::Float(f) => *f,
+        _ => f64::NAN,
+    }
+}
+
+pub fn to_long(value: &Value) -> BigInt {
+    match value {
+        Value::Int(i) => BigInt::from_i64(*i),
+        Value::Long(n) => n.as_ref().clone(),
+        _ => BigInt::zero(),
+    }
+}
+
+pub fn long(n: BigInt) -> Value {
+    Value::Long(Rc::new(n))
+}
+
+fn int_binary(op: TokenType, a: i64, b: i64) -> ExecResult<Value> {
+    let result = match op {
+        TokenType::Plus => a.checked_add(b),
+        TokenType::Minus => a.checked_sub(b),
+        TokenType::Star => a.checked_mul(b),
+        TokenType::Slash | TokenType::Modulo => {
+            if b == 0 {
+                return error("ZeroDivisionError", "integer division or modulo");
+            }
+            int_divmod(a, b).map(|(div, rem)| if op == TokenType::Slash { div } else { rem })
+        }
+        TokenType::StarStar => {
+            if b < 0 {
+                return error("ValueError", "integer to the negative power");
+            }
+            u32::try_from(b).ok().and_then(|exp| a.checked_pow(exp))
+        }
+        TokenType::Ampersand => Some(a & b),
+        TokenType::Pipe => Some(a | b),
+        TokenType::Caret => Some(a ^ b),
+        TokenType::LeftShift | TokenType::RightShift if b < 0 => {
+            return error("ValueError", "negative shift count");
+        }
+        TokenType::LeftShift if a == 0 => Some(0),
+        TokenType::LeftShift => u32::try_from(b)
+            .ok()
+            .and_then(|bits| a.checked_shl(bits))
+            .filter(|shifted| shifted >> b == a),
+        TokenType::RightShift => Some(a >> b.min(63)),
+        _ => {
+            return error("TypeError", format!("unsupported operand type(s) for {op}"));
+        }
+    };
+
+    // Results that overflow an int are promoted to a long, and any that turn
+    // out to fit after all stay ints.
+    match result {
+        Some(value) => Ok(Value::Int(value)),
+        None => match long_binary(op, &BigInt::from_i64(a), &BigInt::from_i64(b))? {
+            Value::Long(n) => Ok(n.to_i64().map_or(Value::Long(n), Value::Int)),
+            other => Ok(other),
+        },
+    }
+}
+
+fn long_binary(op: TokenType, a: &BigInt, b: &BigInt) -> ExecResult<Value> {
+    let result = match op {
+        TokenType::Plus => a + b,
+        TokenType::Minus => a - b,
+        TokenType::Star => a * b,
+        TokenType::Slash | TokenType::Modulo => match a.div_mod_floor(b) {
+            Some((div, rem)) => {
+                if op == TokenType::Slash {
+                    div
+                } else {
+                    rem
+                }
+            }
+            None => return error("ZeroDivisionError", "long division or modulo"),
+        },
+        TokenType::StarStar => {
+            if b.is_negative() {
+                return error("ValueError", "long integer to the negative power");
+            }
+            match b.to_i64().and_then(|exp| u32::try_from(exp).ok()) {
+                Some(exp) => a.pow(exp),
+                None => return error("OverflowError", "long exponent too large"),
+            }
+        }
+        TokenType::Ampersand => a & b,
+        TokenType::Pipe => a | b,
+        TokenType::Caret => a ^ b,
+        TokenType::LeftShift | TokenType::RightShift => {
+            if b.is_negative() {
+                return error("ValueError", "negative shift count");
+            }
+            let Some(bits) = b.to_i64().and_then(|bits| usize::try_from(bits).ok()) else {
+                return error("OverflowError", "outrageous shift count");
+            };
+            if op == TokenType::LeftShift {
+                a << bits
+            } else {
+                a >> bits
+            }
+        }
+        _ => {
+            return error("TypeError", format!("unsupported operand type(s) for {op}"));
+        }
+    };
+
+    Ok(long(result))
+}
+
+pub fn int_divmod(a: i64, b: i64) -> Option<(i64, i64)> {
+    let div = a.checked_div(b)?;
+    let rem = a.checked_rem(b)?;
+
+    if rem != 0 && (rem < 0) != (b < 0) {
+        Some((div - 1, rem + b))
+    } else {
+        Some((div, rem))
+    }
+}
+
+fn float_binary(op: TokenType, a: f64, b: f64) -> ExecResult<Value> {
+    let result = match op {
+        TokenType::Plus => a + b,
+        TokenType::Minus => a - b,
+        TokenType::Star => a * b,
+        TokenType::Slash => {
+            if b == 0.0 {
+                return error("ZeroDivisionError", "float division");
+            }
+            a / b
+        }
+        TokenType::Modulo => {
+            if b == 0.0 {
+                return error("ZeroDivisionError", "float modulo");
+            }
+            float_mod(a, b)
+        }
+        TokenType::StarStar => {
+            if a == 0.0 && b < 0.0 {
+                return error("ZeroDivisionError", "0.0 to a negative power");
+            }
+            if a < 0.0 && b.fract() != 0.0 {
+                return error("ValueError", "negative number to a float power");
+            }
+            a.powf(b)
+        }
+        _ => {
+            return error("TypeError", format!("unsupported operand type(s) for {op}"));
+        }
+    };
+
+    Ok(Value::Float(result))
+}
+
+pub fn float_mod(a: f64, b: f64) -> f64 {
+    let rem = a % b;
+    if rem != 0.0 && (rem < 0.0) != (b < 0.0) {
+        rem + b
+    } else {
+        rem
+    }
+}
+
+pub fn negate(value: &Value) -> ExecResult<Value> {
+    match value {
+        Value::Int(i) => match i.checked_neg() {
+            Some(result) => Ok(Value::Int(result)),
+            None => Ok(long(-&BigInt::from_i64(*i))),
+        },
+        Value::Long(n) => Ok(long(-n.as_ref())),
+        Value::Float(f) => Ok(Value::Float(-f)),
+        _ => error("TypeError", "bad operand type for unary -"),
+    }
+}
+
+pub fn invert(value: &Value) -> ExecResult<Value> {
+    match value {
+        Value::Int(i) => Ok(Value::Int(!i)),
+        Value::Long(n) => Ok(long(!n.as_ref())),
+        _ => error("TypeError", "bad operand type for unary ~"),
+    }
+}
@@ -0,0 +1,439 @@
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, rc::Rc};
+
+use crate::{
+    core::{
+        ast::{Expr, Parameters, Stmt},
+        bigint::BigInt,
+    },
+    diagnostics::source::SourceFile,
+};
+
+use super::{builtins::Builtin, dict::Dict, format::format_float};
+
+pub type Namespace = Rc<RefCell<HashMap<String, Value>>>;
+
+pub fn new_namespace() -> Namespace {
+    Rc::new(RefCell::new(HashMap::new()))
+}
+
+/// A runtime Python object.
+///
+/// Immutable values are shared freely, while lists, dicts, instances and
+/// namespaces are reference-counted cells so that aliases observe mutation.
+#[derive(Clone)]
+pub enum Value {
+    None,
+    Int(i64),
+    Long(Rc<BigInt>),
+    Float(f64),
+    Str(Rc<[u8]>),
+    Tuple(Rc<Vec<Value>>),
+    List(Rc<RefCell<Vec<Value>>>),
+    Dict(Rc<RefCell<Dict>>),
+    Function(Rc<Function>),
+    Builtin(&'static Builtin),
+    Class(Rc<Class>),
+    Instance(Rc<Instance>),
+    Method(Rc<Method>),
+    Module(Rc<Module>),
+    Type(TypeKind),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeKind {
+    None,
+    Int,
+    Long,
+    Float,
+    String,
+    Tuple,
+    List,
+    Dict,
+    Function,
+    Builtin,
+    Class,
+    Instance,
+    Method,
+    Module,
+    Type,
+}
+
+impl TypeKind {
+    pub const ALL: [TypeKind; 15] = [
+        TypeKind::None,
+        TypeKind::Int,
+        TypeKind::Long,
+        TypeKind::Float,
+        TypeKind::String,
+        TypeKind::Tuple,
+        TypeKind::List,
+        TypeKind::Dict,
+        TypeKind::Function,
+        TypeKind::Builtin,
+        TypeKind::Class,
+        TypeKind::Instance,
+        TypeKind::Method,
+        TypeKind::Module,
+        TypeKind::Type,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TypeKind::None => "None",
+            TypeKind::Int => "int",
+            TypeKind::Long => "long int",
+            TypeKind::Float => "float",
+            TypeKind::String => "string",
+            TypeKind::Tuple => "tuple",
+            TypeKind::List => "list",
+            TypeKind::Dict => "dictionary",
+            TypeKind::Function => "function",
+            TypeKind::Builtin => "builtin_function_or_method",
+            TypeKind::Class => "class",
+            TypeKind::Instance => "instance",
+            TypeKind::Method => "instance method",
+            TypeKind::Module => "module",
+            TypeKind::Type => "type",
+        }
+    }
+}
+
+pub enum FunctionBody {
+    Block(Rc<Vec<Stmt>>),
+    Lambda(Rc<Expr>),
+}
+
+pub struct Function {
+    pub name: String,
+    pub params: Parameters,
+    pub defaults: Vec<Value>,
+    pub body: FunctionBody,
+    pub globals: Namespace,
+    pub source: Rc<SourceFile>,
+}
+
+pub struct Class {
+    pub name: String,
+    pub bases: Vec<Rc<Class>>,
+    pub dict: Namespace,
+}
+
+impl Class {
+    pub fn lookup(&self, name: &str) -> Option<Value> {
+        if let Some(value) = self.dict.borrow().get(name) {
+            return Some(value.clone());
+        }
+
+        self.bases.iter().find_map(|base| base.lookup(name))
+    }
+
+    pub fn is_subclass(&self, other: &Class) -> bool {
+        std::ptr::eq(self, other) || self.bases.iter().any(|base| base.is_subclass(other))
+    }
+}
+
+pub struct Instance {
+    pub class: Rc<Class>,
+    pub dict: Namespace,
+}
+
+pub struct Method {
+    pub receiver: Value,
+    pub function: Value,
+}
+
+pub struct Module {
+    pub name: String,
+    pub dict: Namespace,
+}
+
+impl Value {
+    pub fn str(text: &str) -> Self {
+        Value::Str(Rc::from(text.as_bytes()))
+    }
+
+    pub fn bytes(bytes: impl Into<Rc<[u8]>>) -> Self {
+        Value::Str(bytes.into())
+    }
+
+    pub fn tuple(items: Vec<Value>) -> Self {
+        Value::Tuple(Rc::new(items))
+    }
+
+    pub fn list(items: Vec<Value>) -> Self {
+        Value::List(Rc::new(RefCell::new(items)))
+    }
+
+    pub fn dict(dict: Dict) -> Self {
+        Value::Dict(Rc::new(RefCell::new(dict)))
+    }
+
+    pub fn bool(flag: bool) -> Self {
+        Value::Int(flag as i64)
+    }
+
+    pub fn type_of(&self) -> TypeKind {
+        match self {
+            Value::None => TypeKind::None,
+            Value::Int(_) => TypeKind::Int,
+            Value::Long(_) => TypeKind::Long,
+            Value::Float(_) => TypeKind::Float,
+            Value::Str(_) => TypeKind::String,
+            Value::Tuple(_) => TypeKind::Tuple,
+            Value::List(_) => TypeKind::List,
+            Value::Dict(_) => TypeKind::Dict,
+            Value::Function(_) => TypeKind::Function,
+            Value::Builtin(_) => TypeKind::Builtin,
+            Value::Class(_) => TypeKind::Class,
+            Value::Instance(_) => TypeKind::Instance,
+            Value::Method(_) => TypeKind::Method,
+            Value::Module(_) => TypeKind::Module,
+            Value::Type(_) => TypeKind::Type,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_of().name()
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::None => false,
+            Value::Int(i) => *i != 0,
+            Value::Long(n) => !n.is_zero(),
+            Value::Float(f) => *f != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::Tuple(items) => !items.is_empty(),
+            Value::List(items) => !items.borrow().is_empty(),
+            Value::Dict(entries) => !entries.borrow().is_empty(),
+            _ => true,
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        match self {
+            Value::Str(s) => String::from_utf8_lossy(s).into_owned(),
+            _ => self.repr(),
+        }
+    }
+
+    /// The bytes of `str(value)`, which unlike `to_str` keeps strings that
+    /// are not valid UTF-8 intact.
+    pub fn to_bytes(&self) -> Rc<[u8]> {
+        match self {
+            Value::Str(s) => s.clone(),
+            _ => Rc::from(self.repr().as_bytes()),
+        }
+    }
+
+    pub fn repr(&self) -> String {
+        match self {
+            Value::None => "None".to_string(),
+            Value::Int(i) => i.to_string(),
+            Value::Long(n) => format!("{n}L"),
+            Value::Float(f) => format_float(*f),
+            Value::Str(s) => repr_str(s),
+            Value::Tuple(items) => {
+                if items.len() == 1 {
+                    format!("({},)", items[0].repr())
+                } else {
+                    format!("({})", join_reprs(items))
+                }
+            }
+            Value::List(items) => format!("[{}]", join_reprs(&items.borrow())),
+            Value::Dict(entries) => {
+                let pairs = entries
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.repr(), value.repr()))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", pairs.join(", "))
+            }
+            Value::Function(function) => {
+                format!("<function {} at {:x}>", function.name, self.address())
+            }
+            Value::Builtin(builtin) => format!("<built-in function {}>", builtin.name),
+            Value::Class(class) => format!("<class {} at {:x}>", class.name, self.address()),
+            Value::Instance(instance) => {
+                format!("<{} instance at {:x}>", instance.class.name, self.address())
+            }
+            Value::Method(method) => match (&method.receiver, &method.function) {
+                (Value::Instance(instance), Value::Function(function)) => format!(
+                    "<method {}.{} of {} instance at {:x}>",
+                    instance.class.name,
+                    function.name,
+                    instance.class.name,
+                    method.receiver.address()
+                ),
+                (receiver, function) => format!(
+                    "<built-in method {} of {} object at {:x}>",
+                    function.name(),
+                    receiver.type_name(),
+                    receiver.address()
+                ),
+            },
+            Value::Module(module) => format!("<module '{}'>", module.name),
+            Value::Type(kind) => format!("<type '{}'>", kind.name()),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Value::Function(function) => function.name.clone(),
+            Value::Builtin(builtin) => builtin.name.to_string(),
+            Value::Class(class) => class.name.clone(),
+            Value::Module(module) => module.name.clone(),
+            _ => self.type_name().to_string(),
+        }
+    }
+
+    pub fn address(&self) -> usize {
+        match self {
+            Value::Long(n) => Rc::as_ptr(n) as usize,
+            Value::Str(s) => Rc::as_ptr(s) as *const u8 as usize,
+            Value::Tuple(items) => Rc::as_ptr(items) as usize,
+            Value::List(items) => Rc::as_ptr(items) as usize,
+            Value::Dict(entries) => Rc::as_ptr(entries) as usize,
+            Value::Function(function) => Rc::as_ptr(function) as usize,
+            Value::Builtin(builtin) => *builtin as *const Builtin as usize,
+            Value::Class(class) => Rc::as_ptr(class) as usize,
+            Value::Instance(instance) => Rc::as_ptr(instance) as usize,
+            Value::Method(method) => Rc::as_ptr(method) as usize,
+            Value::Module(module) => Rc::as_ptr(module) as usize,
+            Value::None | Value::Int(_) | Value::Float(_) | Value::Type(_) => 0,
+        }
+    }
+
+    /// Returns the object's identity, as exposed by `id()` and compared by `is`.
+    ///
+    /// Heap objects are identified by address. Immediate values have no
+    /// allocation, so equal immediates are treated as the same object and get
+    /// an odd identity holding the whole value, which can never collide with
+    /// a pointer.
+    pub fn id(&self) -> i128 {
+        match self {
+            Value::None => 1,
+            Value::Int(i) => (i128::from(*i) << 3) | 3,
+            Value::Float(f) => (i128::from(f.to_bits()) << 3) | 5,
+            Value::Type(kind) => ((*kind as i128) << 3) | 7,
+            Value::Str(s) => {
+                // Equal strings behave as if interned.
+                let mut hash = 0xcbf2_9ce4_8422_2325u64;
+                for &byte in s.iter() {
+                    hash = (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+                }
+                (i128::from(hash) << 3) | 1
+            }
+            _ => self.address() as i128,
+        }
+    }
+
+    pub fn is(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::None, Value::None) => true,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Type(a), Value::Type(b)) => a == b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+            _ => {
+                std::mem::discriminant(self) == std::mem::discriminant(other)
+                    && self.id() == other.id()
+            }
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        compare(self, other) == Ordering::Equal
+    }
+}
+
+fn compare_floats(x: f64, y: f64) -> Ordering {
+    x.partial_cmp(&y).unwrap_or(Ordering::Equal)
+}
+
+pub fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Int(x), Value::Int(y)) => x.cmp(y),
+        (Value::Int(x), Value::Float(y)) => compare_floats(*x as f64, *y),
+        (Value::Float(x), Value::Int(y)) => compare_floats(*x, *y as f64),
+        (Value::Long(x), Value::Long(y)) => x.cmp(y),
+        (Value::Long(x), Value::Int(y)) => x.as_ref().cmp(&BigInt::from_i64(*y)),
+        (Value::Int(x), Value::Long(y)) => BigInt::from_i64(*x).cmp(y),
+        (Value::Long(x), Value::Float(y)) => compare_floats(x.to_f64(), *y),
+        (Value::Float(x), Value::Long(y)) => compare_floats(*x, y.to_f64()),
+        (Value::Float(x), Value::Float(y)) => compare_floats(*x, *y),
+        (Value::Str(x), Value::Str(y)) => x.cmp(y),
+        (Value::Tuple(x), Value::Tuple(y)) => compare_sequences(x, y),
+        (Value::List(x), Value::List(y)) => {
+            if Rc::ptr_eq(x, y) {
+                return Ordering::Equal;
+            }
+            compare_sequences(&x.borrow(), &y.borrow())
+        }
+        (Value::Dict(x), Value::Dict(y)) => {
+            if Rc::ptr_eq(x, y) {
+                return Ordering::Equal;
+            }
+            let (x, y) = (x.borrow(), y.borrow());
+            x.len().cmp(&y.len()).then_with(|| {
+                let mut x = x.items();
+                let mut y = y.items();
+                x.sort_by(|a, b| compare(&a.0, &b.0));
+                y.sort_by(|a, b| compare(&a.0, &b.0));
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| compare(&a.0, &b.0).then_with(|| compare(&a.1, &b.1)))
+                    .find(|ord| *ord != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            })
+        }
+        (Value::None, Value::None) => Ordering::Equal,
+        _ if std::mem::discriminant(a) == std::mem::discriminant(b) => a.id().cmp(&b.id()),
+        _ => a.type_name().cmp(b.type_name()),
+    }
+}
+
+fn compare_sequences(a: &[Value], b: &[Value]) -> Ordering {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| compare(x, y))
+        .find(|ord| *ord != Ordering::Equal)
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+fn join_reprs(items: &[Value]) -> String {
+    items
+        .iter()
+        .map(|item| item.repr())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn repr_str(s: &[u8]) -> String {
+    let quote = if s.contains(&b'\'') && !s.contains(&b'"') {
+        '"'
+    } else {
+        '\''
+    };
+
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push(quote);
+    for ch in s.iter().copied().map(char::from) {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if (c as u32) < 0x20 || c as u32 >= 0x7f => {
+                out.push_str(&format!("\\{:03o}", c as u32));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+
+    out
+}